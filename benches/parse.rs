@@ -0,0 +1,37 @@
+use chrono::{TimeZone, Utc};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use kal_time::Parser;
+
+// Both inputs are matched by `%Y-%m-%d %H:%M:%S`, but only the first
+// one has the exact shape taking the fast path.
+fn bench_full_datetime(c: &mut Criterion) {
+    let reference = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+    let parser = Parser::new();
+
+    let mut group = c.benchmark_group("full datetime");
+    group.bench_function("fast path", |b| {
+        b.iter(|| parser.parse(black_box("2025-01-27 10:15:00"), &reference))
+    });
+    group.bench_function("formats", |b| {
+        b.iter(|| parser.parse(black_box("2025-1-27 10:15:00"), &reference))
+    });
+    group.finish();
+}
+
+// Inputs read by formats further down the list, each attempt before
+// them tokenizing its format again unless the formats are compiled once
+fn bench_format_list(c: &mut Criterion) {
+    let reference = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+    let parser = Parser::new();
+
+    let mut group = c.benchmark_group("format list");
+    for input in ["10:15", "Jan 5 2024", "20251027T101500+0200"] {
+        group.bench_function(input, |b| {
+            b.iter(|| parser.parse(black_box(input), &reference))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_full_datetime, bench_format_list);
+criterion_main!(benches);
@@ -4,49 +4,145 @@ use std::process;
 
 use chrono::{DateTime, FixedOffset};
 use kal_time::{
-    parse, parse_timespan, parse_timespan_with_reference, parse_with_reference,
+    Rounding, parse, parse_duration, parse_timespan, parse_timespan_with_reference,
+    parse_with_reference, round_to,
 };
 
 fn main() {
+    // known before the other arguments, so that their errors are JSON too
+    let json = env::args().skip(1).any(|arg| arg == "--json");
     if let Err(err) = run() {
-        eprintln!("{err}");
+        if json {
+            println!("{{\"error\": {}}}", json_string(&err));
+        } else {
+            eprintln!("{err}");
+        }
         process::exit(1);
     }
 }
 
+/// Number of fractional second digits printed after the seconds field.
+#[derive(Clone, Copy)]
+enum Precision {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl Precision {
+    fn from_flag(s: &str) -> Result<Self, String> {
+        match s {
+            "s" => Ok(Precision::Seconds),
+            "ms" => Ok(Precision::Millis),
+            "us" => Ok(Precision::Micros),
+            "ns" => Ok(Precision::Nanos),
+            _ => Err(format!(
+                "Invalid precision {s:?} (expected s, ms, us or ns)"
+            )),
+        }
+    }
+
+    fn format(self) -> &'static str {
+        match self {
+            Precision::Seconds => "%Y-%m-%d %H:%M:%S %:z",
+            Precision::Millis => "%Y-%m-%d %H:%M:%S%.3f %:z",
+            Precision::Micros => "%Y-%m-%d %H:%M:%S%.6f %:z",
+            Precision::Nanos => "%Y-%m-%d %H:%M:%S%.9f %:z",
+        }
+    }
+}
+
 fn run() -> Result<(), String> {
-    let args: Vec<String> = env::args().skip(1).collect();
+    let mut precision = Precision::Seconds;
+    let mut reference_flag = None;
+    let mut rounding = Rounding::Nearest;
+    let mut json = false;
+    let mut args: Vec<String> = Vec::new();
+    let mut raw = env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        if let Some(value) = arg.strip_prefix("--precision=") {
+            precision = Precision::from_flag(value)?;
+        } else if arg == "--precision" {
+            let value = raw.next().ok_or_else(usage)?;
+            precision = Precision::from_flag(&value)?;
+        } else if let Some(value) = arg.strip_prefix("--reference=") {
+            reference_flag = Some(value.to_string());
+        } else if arg == "--reference" {
+            reference_flag = Some(raw.next().ok_or_else(usage)?);
+        } else if arg == "--floor" {
+            rounding = Rounding::Floor;
+        } else if arg == "--ceil" {
+            rounding = Rounding::Ceil;
+        } else if arg == "--json" {
+            json = true;
+        } else {
+            args.push(arg);
+        }
+    }
 
-    if args.len() < 2 || args.len() > 3 {
+    let action = args.first().ok_or_else(usage)?;
+    // `round` takes the step before the optional reference
+    let positional = if action == "round" { 3 } else { 2 };
+    if args.len() < positional || args.len() > positional + 1 {
         return Err(usage());
     }
 
-    let action = &args[0];
     let input = &args[1];
-    let reference = if let Some(reference_str) = args.get(2) {
-        Some(parse_reference(reference_str).map_err(|e| format!("Invalid reference time: {e}"))?)
-    } else {
-        None
+    let reference = match (reference_flag.as_ref(), args.get(positional)) {
+        (Some(_), Some(_)) => {
+            return Err("Give the reference either with --reference or as an argument".into());
+        }
+        (Some(reference_str), None) | (None, Some(reference_str)) => Some(
+            parse_reference(reference_str).map_err(|e| format!("Invalid reference time: {e}"))?,
+        ),
+        (None, None) => None,
+    };
+
+    let parse_time = |input: &str| {
+        match reference {
+            Some(ref_dt) => parse_with_reference(input, &ref_dt),
+            None => parse(input),
+        }
+        .map_err(|e| format!("Failed to parse time: {e}"))
     };
 
     match action.as_str() {
         "time" => {
-            let dt = match reference {
-                Some(ref_dt) => parse_with_reference(input, &ref_dt)
-                    .map_err(|e| format!("Failed to parse time: {e}"))?,
-                None => parse(input).map_err(|e| format!("Failed to parse time: {e}"))?,
-            };
-            println!("{}", format_timestamp(&dt));
+            let dt = parse_time(input)?;
+            if json {
+                println!("{}", json_timestamp(&dt, precision));
+            } else {
+                println!("{}", format_timestamp(&dt, precision));
+            }
+        }
+        "round" => {
+            let step = parse_duration(&args[2]).map_err(|e| format!("Invalid step: {e}"))?;
+            let dt = round_to(&parse_time(input)?, step, rounding).map_err(|e| e.to_string())?;
+            if json {
+                println!("{}", json_timestamp(&dt, precision));
+            } else {
+                println!("{}", format_timestamp(&dt, precision));
+            }
         }
         "timespan" => {
             let (start, stop) = match reference {
                 Some(ref_dt) => parse_timespan_with_reference(input, &ref_dt)
                     .map_err(|e| format!("Failed to parse timespan: {e}"))?,
-                None => parse_timespan(input)
-                    .map_err(|e| format!("Failed to parse timespan: {e}"))?,
+                None => {
+                    parse_timespan(input).map_err(|e| format!("Failed to parse timespan: {e}"))?
+                }
             };
-            println!("{}", format_timestamp(&start));
-            println!("{}", format_timestamp(&stop));
+            if json {
+                println!(
+                    "{{\"start\": {}, \"stop\": {}}}",
+                    json_timestamp(&start, precision),
+                    json_timestamp(&stop, precision)
+                );
+            } else {
+                println!("{}", format_timestamp(&start, precision));
+                println!("{}", format_timestamp(&stop, precision));
+            }
         }
         _ => return Err(usage()),
     }
@@ -55,10 +151,12 @@ fn run() -> Result<(), String> {
 }
 
 fn usage() -> String {
-    let mut msg = String::from("Usage: kt-parse <time|timespan> <input> [reference]");
+    let mut msg = String::from(
+        "Usage: kt-parse [--json] [--precision s|ms|us|ns] [--reference <time>] <time|timespan> <input> [reference]\n       kt-parse [--json] [--floor|--ceil] [--reference <time>] round <input> <step> [reference]",
+    );
     let _ = write!(
         msg,
-        "\n  <input>: time or timespan string accepted by kal-time\n  [reference]: fully specified timestamp with timezone (e.g. 2025-10-22T09:10:11+00:00)\n"
+        "\n  <input>: time or timespan string accepted by kal-time\n  [reference]: fully specified timestamp with timezone (e.g. 2025-10-22T09:10:11+00:00),\n    or any time kal-time accepts, resolved against the current clock\n  --reference: same as [reference]\n  --precision: fractional second digits to print (default: s)\n  <step>: duration to round to (e.g. 15m), to the nearest multiple unless\n    --floor or --ceil is given\n  --json: print results, and errors, as JSON objects on stdout\n"
     );
     msg
 }
@@ -81,13 +179,34 @@ fn parse_reference(s: &str) -> Result<DateTime<FixedOffset>, String> {
         }
     }
 
-    Err(format!("Unable to parse reference timestamp: {s}"))
+    parse(s).map_err(|e| format!("Unable to parse reference timestamp: {e}"))
 }
 
-fn format_timestamp(dt: &DateTime<FixedOffset>) -> String {
+fn format_timestamp(dt: &DateTime<FixedOffset>, precision: Precision) -> String {
+    format!("{} {}", dt.timestamp(), dt.format(precision.format()))
+}
+
+fn json_timestamp(dt: &DateTime<FixedOffset>, precision: Precision) -> String {
     format!(
-        "{} {}",
+        "{{\"timestamp\": {}, \"time\": {}}}",
         dt.timestamp(),
-        dt.format("%Y-%m-%d %H:%M:%S %:z")
+        json_string(&dt.format(precision.format()).to_string())
     )
 }
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
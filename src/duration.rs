@@ -0,0 +1,651 @@
+use chrono::{DateTime, Duration, Months, TimeZone};
+
+use crate::ParseError;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Digits kept after the decimal point; anything finer than a
+/// nanosecond is dropped.
+const MAX_FRACTION_DIGITS: usize = 9;
+
+fn unit_nanos(unit: &str) -> Option<i128> {
+    let nanos = match unit {
+        "ns" => 1,
+        "us" => 1_000,
+        "ms" => 1_000_000,
+        "s" | "sec" | "secs" | "second" | "seconds" => NANOS_PER_SECOND,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60 * NANOS_PER_SECOND,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600 * NANOS_PER_SECOND,
+        "d" | "day" | "days" => 86400 * NANOS_PER_SECOND,
+        "w" | "week" | "weeks" => 7 * 86400 * NANOS_PER_SECOND,
+        _ => return None,
+    };
+    Some(nanos)
+}
+
+// Value of `<int>.<frac>` units in nanoseconds. The fractional part
+// carries into the smaller units, so `30.5m` is 30 minutes 30 seconds.
+fn component_nanos(int: &str, frac: &str, unit: i128) -> Option<i128> {
+    let int: i128 = if int.is_empty() { 0 } else { int.parse().ok()? };
+    let mut nanos = int.checked_mul(unit)?;
+    if !frac.is_empty() {
+        let frac = &frac[..frac.len().min(MAX_FRACTION_DIGITS)];
+        let scale = 10i128.pow(frac.len() as u32);
+        nanos = nanos.checked_add(frac.parse::<i128>().ok()? * unit / scale)?;
+    }
+    Some(nanos)
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()))
+}
+
+/// Parse a length of time such as `90m`, `1h30m`, `1h 30m` or `30.5m`.
+///
+/// Each component is a number, optionally with a decimal part, followed
+/// by one of the units `w`, `d`, `h`, `m`, `s`, `ms`, `us` or `ns`. The
+/// units from seconds up may also be spelled out (`2hours`, `30min`).
+/// Unknown units and lengths beyond what `Duration` holds are errors.
+///
+/// ISO 8601 durations (`PT30M`, `P1DT2H3M4S`) are accepted too, as long
+/// as they have no years or months: those have no fixed length, and
+/// are only meaningful from a given date, as the end of a timespan
+/// (`2025-01-31..P1M`) or with `parse_calendar_duration`.
+///
+/// A leading `-` negates the whole duration: `-1h30m` is minus 90
+/// minutes, not minus an hour plus 30 minutes.
+pub fn parse_duration(s: &str) -> Result<Duration, ParseError> {
+    match s.trim().strip_prefix('-') {
+        Some(magnitude) => unsigned_duration(s, magnitude).map(|d| -d),
+        None => unsigned_duration(s, s),
+    }
+}
+
+// `parse_duration` without the sign; errors quote `input`, the whole
+// string
+fn unsigned_duration(input: &str, s: &str) -> Result<Duration, ParseError> {
+    if let Some(iso) = parse_iso(s.trim()) {
+        return match iso? {
+            CalendarDuration { months: 0, fixed } => Ok(fixed),
+            _ => Err(ParseError::CalendarDuration {
+                input: input.to_string(),
+            }),
+        };
+    }
+    let compact: String = s.split_whitespace().collect();
+    total_nanos(&compact, false)
+        .and_then(to_duration)
+        .ok_or_else(|| ParseError::InvalidDuration {
+            input: input.to_string(),
+        })
+}
+
+/// Recognize a signed offset such as `+1d12h` or `-2h30m`.
+///
+/// The sign applies to the whole duration, whose units must go from the
+/// largest to the smallest, each appearing once. Returns `None` when
+/// `s` doesn't start with a sign, or has characters no duration has,
+/// such as the dashes of a negative year (`-0043-01-01`), so that the
+/// formats get to read it.
+pub(crate) fn parse_offset(s: &str) -> Option<Result<Duration, ParseError>> {
+    let (negative, rest) = match s.strip_prefix('+') {
+        Some(rest) => (false, rest),
+        None => (true, s.strip_prefix('-')?),
+    };
+    if !rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+        return None;
+    }
+    let total = total_nanos(rest, true).and_then(|nanos| {
+        if negative {
+            nanos.checked_neg()
+        } else {
+            Some(nanos)
+        }
+    });
+    Some(
+        total
+            .and_then(to_duration)
+            .ok_or_else(|| ParseError::InvalidOffset {
+                input: s.to_string(),
+            }),
+    )
+}
+
+/// Like `parse_offset`, but the duration may start with calendar years
+/// (`y`) and months (`mo`), in that order: `+1mo`, `-1y`, `+1y2mo3d`.
+pub(crate) fn parse_calendar_offset(s: &str) -> Option<Result<CalendarDuration, ParseError>> {
+    let (sign, mut rest) = s.split_at_checked(1)?;
+    if sign != "+" && sign != "-" {
+        return None;
+    }
+    let invalid = || ParseError::InvalidOffset {
+        input: s.to_string(),
+    };
+
+    let mut months: i64 = 0;
+    for (unit, months_per_unit) in [("y", 12), ("mo", 1)] {
+        let (int, tail) = split_digits(rest);
+        let Some(tail) = tail.strip_prefix(unit) else {
+            continue;
+        };
+        if int.is_empty() || tail.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        let Some(total) = int
+            .parse::<i64>()
+            .ok()
+            .and_then(|n| n.checked_mul(months_per_unit))
+            .and_then(|n| months.checked_add(n))
+        else {
+            return Some(Err(invalid()));
+        };
+        months = total;
+        rest = tail;
+    }
+
+    let fixed = if months != 0 && rest.is_empty() {
+        Duration::zero()
+    } else {
+        match parse_offset(&format!("{}{}", sign, rest))? {
+            Ok(fixed) => fixed,
+            Err(_) => return Some(Err(invalid())),
+        }
+    };
+    if sign == "-" {
+        months = -months;
+    }
+    Some(Ok(CalendarDuration { months, fixed }))
+}
+
+/// A length of time counting calendar months, whose length depends on
+/// the date they are added to, on top of a fixed duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDuration {
+    /// Months, years counting as 12.
+    pub months: i64,
+    pub fixed: Duration,
+}
+
+impl CalendarDuration {
+    /// `dt` shifted by the months on its wall clock, then by the fixed
+    /// duration. A day missing from the target month is clamped to its
+    /// last day: January 31st plus one month is February 28th (or
+    /// 29th).
+    ///
+    /// Returns `None` when the result is out of range, or when the
+    /// shifted wall-clock time doesn't exist or is ambiguous in `dt`'s
+    /// timezone.
+    pub fn add_to<Tz: TimeZone>(&self, dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let months = Months::new(u32::try_from(self.months.unsigned_abs()).ok()?);
+        let shifted = if self.months >= 0 {
+            dt.clone().checked_add_months(months)?
+        } else {
+            dt.clone().checked_sub_months(months)?
+        };
+        shifted.checked_add_signed(self.fixed)
+    }
+}
+
+/// Parse a length of time that may count calendar months, such as the
+/// ISO 8601 `P1M`, `P1Y` or `P1Y2M3DT4H`. Anything `parse_duration`
+/// accepts is accepted too, with no months.
+pub fn parse_calendar_duration(s: &str) -> Result<CalendarDuration, ParseError> {
+    match parse_iso(s.trim()) {
+        Some(iso) => iso,
+        None => parse_duration(s).map(|fixed| CalendarDuration { months: 0, fixed }),
+    }
+}
+
+/// Recognize an ISO 8601 duration such as `PT30M`, `P1Y2M` or
+/// `P1DT2H3M4S`. Years count as 12 months; weeks, days and time
+/// components may have a decimal part. Returns `None` when `s` doesn't
+/// start with `P`.
+pub(crate) fn parse_iso(s: &str) -> Option<Result<CalendarDuration, ParseError>> {
+    let body = s.strip_prefix('P')?;
+    Some(
+        iso_duration(body).ok_or_else(|| ParseError::InvalidDuration {
+            input: s.to_string(),
+        }),
+    )
+}
+
+fn iso_duration(body: &str) -> Option<CalendarDuration> {
+    let (date, time) = match body.split_once('T') {
+        Some((_, "")) => return None,
+        Some((date, time)) => (iso_components(date, "YMWD")?, iso_components(time, "HMS")?),
+        None => (iso_components(body, "YMWD")?, Vec::new()),
+    };
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+
+    let mut months: i64 = 0;
+    let mut nanos: i128 = 0;
+    for (designator, int, frac) in date {
+        let unit = match designator {
+            'Y' | 'M' if !frac.is_empty() => return None,
+            'Y' => {
+                months = months.checked_add(int.parse::<i64>().ok()?.checked_mul(12)?)?;
+                continue;
+            }
+            'M' => {
+                months = months.checked_add(int.parse().ok()?)?;
+                continue;
+            }
+            'W' => unit_nanos("w")?,
+            _ => unit_nanos("d")?,
+        };
+        nanos = nanos.checked_add(component_nanos(int, frac, unit)?)?;
+    }
+    for (designator, int, frac) in time {
+        let unit = unit_nanos(&designator.to_ascii_lowercase().to_string())?;
+        nanos = nanos.checked_add(component_nanos(int, frac, unit)?)?;
+    }
+    log::trace!("ISO duration of {} months and {}ns", months, nanos);
+    Some(CalendarDuration {
+        months,
+        fixed: to_duration(nanos)?,
+    })
+}
+
+// `<number><designator>` components of one side of the `T`, whose
+// designators must appear in the order of `designators`, each at most
+// once.
+fn iso_components<'a>(mut s: &'a str, designators: &str) -> Option<Vec<(char, &'a str, &'a str)>> {
+    let mut allowed = designators;
+    let mut components = Vec::new();
+    while !s.is_empty() {
+        let (int, tail) = split_digits(s);
+        let (frac, tail) = match tail.strip_prefix(['.', ',']) {
+            Some(tail) => split_digits(tail),
+            None => ("", tail),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        let designator = tail.chars().next()?;
+        allowed = &allowed[allowed.find(designator)? + 1..];
+        components.push((designator, int, frac));
+        s = &tail[designator.len_utf8()..];
+    }
+    Some(components)
+}
+
+/// Recognize `in <duration>` and `<duration> ago`, such as `in 15m`
+/// or `1 hour 30 minutes ago`, returning the duration negated for
+/// `ago`. Returns `None` when `s` has neither word, or when the rest
+/// is not a number with a unit, as in `in 2025`.
+pub(crate) fn parse_spoken_offset(s: &str) -> Option<Result<Duration, ParseError>> {
+    let lower = s.trim().to_lowercase();
+    let (body, negative) = match (lower.strip_prefix("in "), lower.strip_suffix(" ago")) {
+        (Some(body), _) => (body, false),
+        (None, Some(body)) => (body, true),
+        (None, None) => return None,
+    };
+    let compact: String = body.split_whitespace().collect();
+    if !compact.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        || !compact.contains(|c: char| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    let total = total_nanos(&compact, false).and_then(|nanos| {
+        if negative {
+            nanos.checked_neg()
+        } else {
+            Some(nanos)
+        }
+    });
+    Some(
+        total
+            .and_then(to_duration)
+            .ok_or_else(|| ParseError::InvalidDuration {
+                input: s.to_string(),
+            }),
+    )
+}
+
+// Sum of the components of `s` in nanoseconds. With `ordered`, units
+// must be strictly decreasing, which also rejects repeated units.
+fn total_nanos(s: &str, ordered: bool) -> Option<i128> {
+    if s.is_empty() {
+        return None;
+    }
+
+    let mut total: i128 = 0;
+    let mut previous_unit = i128::MAX;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let (int, tail) = split_digits(rest);
+        let (frac, tail) = match tail.strip_prefix('.') {
+            Some(tail) => split_digits(tail),
+            None => ("", tail),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        let unit_len = tail
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let unit = unit_nanos(unit)?;
+        if ordered && unit >= previous_unit {
+            log::trace!("Duration unit out of order in {:?}", s);
+            return None;
+        }
+        previous_unit = unit;
+
+        log::trace!("Duration component {:?}.{:?} x {}ns", int, frac, unit);
+        total = total.checked_add(component_nanos(int, frac, unit)?)?;
+        rest = tail;
+    }
+    Some(total)
+}
+
+fn to_duration(total: i128) -> Option<Duration> {
+    let seconds = i64::try_from(total.div_euclid(NANOS_PER_SECOND)).ok()?;
+    let nanos = total.rem_euclid(NANOS_PER_SECOND) as i64;
+    Duration::try_seconds(seconds)?.checked_add(&Duration::nanoseconds(nanos))
+}
+
+/// `d` in words, largest units first: `1 day, 2 hours, 30 minutes`.
+/// Weeks are counted in days and a fraction of a second is kept as
+/// decimals (`1.5 seconds`).
+pub(crate) fn describe_duration(d: Duration) -> String {
+    let sign = if d < Duration::zero() { "-" } else { "" };
+    let d = d.abs();
+    let seconds = d.num_seconds() % 60;
+    let nanos = d.subsec_nanos();
+    let mut parts: Vec<String> = [
+        (d.num_days(), "day"),
+        (d.num_hours() % 24, "hour"),
+        (d.num_minutes() % 60, "minute"),
+    ]
+    .into_iter()
+    .filter(|(n, _)| *n != 0)
+    .map(|(n, unit)| format!("{n} {unit}{}", if n == 1 { "" } else { "s" }))
+    .collect();
+    if nanos != 0 {
+        let fraction = format!("{nanos:09}");
+        parts.push(format!(
+            "{seconds}.{} seconds",
+            fraction.trim_end_matches('0')
+        ));
+    } else if seconds != 0 || parts.is_empty() {
+        let plural = if seconds == 1 { "" } else { "s" };
+        parts.push(format!("{seconds} second{plural}"));
+    }
+    format!("{sign}{}", parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(parse_duration("90m"), Ok(Duration::minutes(90)));
+        assert_eq!(
+            parse_duration("1h30m"),
+            Ok(Duration::hours(1) + Duration::minutes(30))
+        );
+        assert_eq!(parse_duration("2d"), Ok(Duration::days(2)));
+        assert_eq!(parse_duration("1w"), Ok(Duration::days(7)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::milliseconds(500)));
+        assert_eq!(parse_duration("1.5ms"), Ok(Duration::microseconds(1500)));
+        assert_eq!(parse_duration("20us"), Ok(Duration::microseconds(20)));
+        assert_eq!(parse_duration("7ns"), Ok(Duration::nanoseconds(7)));
+        assert_eq!(parse_duration("1s500ms"), Ok(Duration::milliseconds(1500)));
+    }
+
+    #[test]
+    fn test_iso() {
+        assert_eq!(parse_duration("PT30M"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_duration("P1D"), Ok(Duration::days(1)));
+        assert_eq!(parse_duration("P2W"), Ok(Duration::weeks(2)));
+        assert_eq!(
+            parse_duration("P1DT2H3M4S"),
+            Ok(Duration::days(1) + Duration::seconds(2 * 3600 + 3 * 60 + 4))
+        );
+        assert_eq!(parse_duration("PT0.5S"), Ok(Duration::milliseconds(500)));
+        assert_eq!(parse_duration("PT1,5H"), Ok(Duration::minutes(90)));
+
+        let calendar = parse_duration("P1M").unwrap_err();
+        assert_eq!(
+            calendar,
+            ParseError::CalendarDuration {
+                input: "P1M".to_string()
+            }
+        );
+        assert!(calendar.to_string().contains("no fixed length"));
+        assert!(matches!(
+            parse_duration("P1Y2M"),
+            Err(ParseError::CalendarDuration { .. })
+        ));
+
+        assert_eq!(
+            parse_iso("P1Y2MT1H"),
+            Some(Ok(CalendarDuration {
+                months: 14,
+                fixed: Duration::hours(1)
+            }))
+        );
+        assert_eq!(parse_iso("1D"), None);
+        for invalid in [
+            "P", "PT", "P1DT", "PT1D", "P1H", "P1D1Y", "P1M1M", "P1.5M", "PT1",
+        ] {
+            assert_eq!(
+                parse_duration(invalid),
+                Err(ParseError::InvalidDuration {
+                    input: invalid.to_string()
+                }),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_calendar_duration() {
+        let month = parse_calendar_duration("P1M").unwrap();
+        assert_eq!(
+            month,
+            CalendarDuration {
+                months: 1,
+                fixed: Duration::zero()
+            }
+        );
+        assert_eq!(parse_calendar_duration("P1Y").map(|d| d.months), Ok(12));
+        assert_eq!(
+            parse_calendar_duration("P1Y2M3DT4H"),
+            Ok(CalendarDuration {
+                months: 14,
+                fixed: Duration::days(3) + Duration::hours(4)
+            })
+        );
+        assert_eq!(
+            parse_calendar_duration("1h30m"),
+            Ok(CalendarDuration {
+                months: 0,
+                fixed: Duration::minutes(90)
+            })
+        );
+        assert!(parse_calendar_duration("P1X").is_err());
+
+        let jan31 = chrono::Utc.with_ymd_and_hms(2024, 1, 31, 10, 0, 0).unwrap();
+        assert_eq!(
+            month.add_to(&jan31),
+            Some(chrono::Utc.with_ymd_and_hms(2024, 2, 29, 10, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_calendar_duration("P1Y2M3DT4H")
+                .unwrap()
+                .add_to(&jan31),
+            Some(chrono::Utc.with_ymd_and_hms(2025, 4, 3, 14, 0, 0).unwrap())
+        );
+        let back = CalendarDuration {
+            months: -1,
+            fixed: Duration::zero(),
+        };
+        assert_eq!(
+            back.add_to(&jan31),
+            Some(
+                chrono::Utc
+                    .with_ymd_and_hms(2023, 12, 31, 10, 0, 0)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_calendar_offset() {
+        let calendar = |months, fixed| Some(Ok(CalendarDuration { months, fixed }));
+        assert_eq!(parse_calendar_offset("+1mo"), calendar(1, Duration::zero()));
+        assert_eq!(
+            parse_calendar_offset("-1y"),
+            calendar(-12, Duration::zero())
+        );
+        assert_eq!(
+            parse_calendar_offset("+1y2mo3d"),
+            calendar(14, Duration::days(3))
+        );
+        assert_eq!(
+            parse_calendar_offset("-2mo1h"),
+            calendar(-2, Duration::hours(-1))
+        );
+        assert_eq!(
+            parse_calendar_offset("+5m"),
+            calendar(0, Duration::minutes(5))
+        );
+        assert_eq!(parse_calendar_offset("1mo"), None);
+        for invalid in ["+", "+1mo1y", "+1month", "+99999999999999999999y", "+1y1y"] {
+            assert_eq!(
+                parse_calendar_offset(invalid),
+                Some(Err(ParseError::InvalidOffset {
+                    input: invalid.to_string()
+                })),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_whitespace_separated() {
+        assert_eq!(
+            parse_duration("1h 30m"),
+            Ok(Duration::hours(1) + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_duration(" 2d  3h "),
+            Ok(Duration::days(2) + Duration::hours(3))
+        );
+    }
+
+    #[test]
+    fn test_decimal_carries_into_smaller_unit() {
+        assert_eq!(
+            parse_duration("30.5m"),
+            Ok(Duration::minutes(30) + Duration::seconds(30))
+        );
+        assert_eq!(
+            parse_duration("1.5h"),
+            Ok(Duration::hours(1) + Duration::minutes(30))
+        );
+        assert_eq!(parse_duration("0.5s"), Ok(Duration::milliseconds(500)));
+        assert_eq!(parse_duration(".25h"), Ok(Duration::minutes(15)));
+    }
+
+    #[test]
+    fn test_describe_duration() {
+        assert_eq!(describe_duration(Duration::hours(2)), "2 hours");
+        assert_eq!(
+            describe_duration(Duration::days(8) + Duration::minutes(1)),
+            "8 days, 1 minute"
+        );
+        assert_eq!(
+            describe_duration(Duration::milliseconds(1500)),
+            "1.5 seconds"
+        );
+        assert_eq!(describe_duration(-Duration::seconds(45)), "-45 seconds");
+        assert_eq!(describe_duration(Duration::zero()), "0 seconds");
+    }
+
+    #[test]
+    fn test_negative() {
+        assert_eq!(
+            parse_duration("-1h30m"),
+            Ok(-(Duration::hours(1) + Duration::minutes(30)))
+        );
+        assert_eq!(parse_duration("-45s"), Ok(Duration::seconds(-45)));
+        assert_eq!(parse_duration(" -1h 30m"), parse_duration("-90m"));
+        assert_eq!(parse_duration("-PT1H"), Ok(Duration::hours(-1)));
+        assert_eq!(
+            parse_duration("1h-30m"),
+            Err(ParseError::InvalidDuration {
+                input: "1h-30m".to_string()
+            })
+        );
+        assert!(parse_duration("--1h").is_err());
+        assert!(parse_duration("-").is_err());
+    }
+
+    #[test]
+    fn test_err() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("99999999999999999999999w").is_err());
+        // past the limits of `Duration`, but not of the i128 sum
+        assert!(parse_duration("99999999999999w").is_err());
+        assert!(parse_duration("1h 30").is_err());
+    }
+
+    #[test]
+    fn test_spoken_offset() {
+        assert_eq!(
+            parse_spoken_offset("in 15m"),
+            Some(Ok(Duration::minutes(15)))
+        );
+        assert_eq!(
+            parse_spoken_offset("5 minutes ago"),
+            Some(Ok(Duration::minutes(-5)))
+        );
+        assert_eq!(
+            parse_spoken_offset("In 1 hour 30 mins"),
+            Some(Ok(Duration::minutes(90)))
+        );
+        assert_eq!(
+            parse_spoken_offset("2d3h ago"),
+            Some(Ok(-(Duration::days(2) + Duration::hours(3))))
+        );
+        assert!(matches!(parse_spoken_offset("in 5 parsecs"), Some(Err(_))));
+        assert_eq!(parse_spoken_offset("in ago"), None);
+        assert_eq!(parse_spoken_offset("in 2025"), None);
+        assert_eq!(parse_spoken_offset("in March"), None);
+        assert_eq!(parse_spoken_offset("2025 ago"), None);
+        assert_eq!(parse_spoken_offset("15m"), None);
+        assert_eq!(parse_spoken_offset("chicago"), None);
+    }
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(
+            parse_offset("+1d12h"),
+            Some(Ok(Duration::days(1) + Duration::hours(12)))
+        );
+        assert_eq!(
+            parse_offset("-2h30m"),
+            Some(Ok(-(Duration::hours(2) + Duration::minutes(30))))
+        );
+        assert_eq!(
+            parse_offset("-0.5s"),
+            Some(Ok(Duration::milliseconds(-500)))
+        );
+        assert_eq!(parse_offset("+1w3d"), Some(Ok(Duration::days(10))));
+        assert_eq!(parse_offset("1d"), None);
+        assert!(matches!(parse_offset("+30m1h"), Some(Err(_))));
+        assert!(matches!(parse_offset("+1h1h"), Some(Err(_))));
+        assert!(matches!(parse_offset("+"), Some(Err(_))));
+        assert_eq!(parse_offset("-0043-01-01"), None);
+        assert_eq!(parse_offset("+10:00"), None);
+    }
+}
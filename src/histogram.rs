@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone};
+
+use crate::{ParseError, Parser, Rounding, round_to};
+
+/// What to do with inputs that can't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Malformed {
+    /// Count them in `Histogram::malformed`.
+    #[default]
+    Count,
+    /// Ignore them.
+    Skip,
+}
+
+/// Number of inputs falling in each bucket, keyed by the bucket start.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    pub buckets: BTreeMap<DateTime<FixedOffset>, usize>,
+    pub malformed: usize,
+}
+
+/// Parse each of `inputs` against `reference` and count them in
+/// buckets of length `bucket`.
+///
+/// Buckets are aligned on the wall-clock time of each result, so hourly
+/// buckets start on the hour even in `+05:30`. Empty buckets are left
+/// out.
+pub fn histogram<Tz, I>(
+    inputs: I,
+    reference: &DateTime<Tz>,
+    bucket: Duration,
+    malformed: Malformed,
+) -> Result<Histogram, ParseError>
+where
+    Tz: TimeZone,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let parser = Parser::new();
+    let mut histogram = Histogram::default();
+    for input in inputs {
+        let input = input.as_ref();
+        let dt = match parser.parse(input, reference) {
+            Ok(dt) => dt,
+            Err(err) => {
+                log::trace!("Skipping {:?}: {}", input, err);
+                if malformed == Malformed::Count {
+                    histogram.malformed += 1;
+                }
+                continue;
+            }
+        };
+        let start = round_to(&dt, bucket, Rounding::Floor)?;
+        *histogram.buckets.entry(start).or_default() += 1;
+    }
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_hourly_histogram() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 27, 12, 0, 0).unwrap();
+        let inputs = [
+            "10:05",
+            "10:59:59",
+            "11:00",
+            "toto",
+            "2025-10-27 13:30",
+            "10:30",
+        ];
+
+        let histogram =
+            histogram(inputs, &reference, Duration::hours(1), Malformed::Count).unwrap();
+        let buckets: Vec<_> = histogram
+            .buckets
+            .iter()
+            .map(|(start, count)| (start.format("%H:%M").to_string(), *count))
+            .collect();
+        assert_eq!(
+            buckets,
+            [
+                ("10:00".to_string(), 3),
+                ("11:00".to_string(), 1),
+                ("13:00".to_string(), 1),
+            ]
+        );
+        assert_eq!(histogram.malformed, 1);
+
+        let skipped = super::histogram(inputs, &reference, Duration::hours(1), Malformed::Skip);
+        assert_eq!(skipped.map(|h| h.malformed), Ok(0));
+        assert!(super::histogram(inputs, &reference, Duration::zero(), Malformed::Skip).is_err());
+    }
+}
@@ -1,93 +1,540 @@
-use chrono::{DateTime, FixedOffset, TimeZone};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone};
 use lazy_static::lazy_static;
 
+mod duration;
+mod histogram;
+mod locale;
 mod parse;
+mod parser;
+mod relative;
+mod round;
+#[cfg(feature = "test-util")]
+mod test_util;
+mod timespan;
+
+pub use duration::{CalendarDuration, parse_calendar_duration, parse_duration};
+pub use histogram::{Histogram, Malformed, histogram};
+pub use locale::{DateOrder, Locale};
+pub use parse::ParseDetails;
+pub use parser::{
+    AmbiguousPolicy, Fill, LeapSecond, ParseOutcome, Parser, ReversedSpan, SupportedSyntax,
+    TimeToken, YearInference,
+};
+pub use relative::WeekStart;
+pub use round::{Rounding, round_to};
+#[cfg(feature = "test-util")]
+pub use test_util::assert_roundtrip;
+#[cfg(feature = "chrono-tz")]
+pub use timespan::dst_transitions_in;
+pub use timespan::{
+    Timespan, describe_timespan, expand_timespan, iter_days, iter_months, shift_timespan,
+    timespan_intersection, timespan_overlap, timespan_split,
+};
+
+/// Why an input could not be parsed.
+///
+/// The `Display` output is a human-readable message quoting the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// No format nor expression matched the input. `tried` lists the
+    /// formats attempted when `Parser::verbose_errors` is set.
+    NoFormatMatched {
+        input: String,
+        tried: Vec<String>,
+    },
+    /// The result would leave the range `chrono` can represent.
+    OutOfRange {
+        input: String,
+    },
+    /// The wall-clock time falls in a DST gap.
+    NonexistentLocal {
+        input: String,
+    },
+    /// The wall-clock time happens twice (DST fold) and the parser is
+    /// set to `AmbiguousPolicy::Reject`.
+    AmbiguousLocal {
+        input: String,
+        earlier: DateTime<FixedOffset>,
+        later: DateTime<FixedOffset>,
+    },
+    /// A numeric UTC offset is followed by other fields.
+    MisplacedOffset {
+        input: String,
+    },
+    /// A duration such as `90m` or `PT1H` is malformed.
+    InvalidDuration {
+        input: String,
+    },
+    /// A signed offset such as `+1d12h` is malformed.
+    InvalidOffset {
+        input: String,
+    },
+    /// `Parser::require_explicit_range` is set and the timespan has no
+    /// separator.
+    MissingSeparator {
+        input: String,
+        separator: String,
+    },
+    /// Both sides of an open timespan are empty (`..`), and
+    /// `Parser::allow_fully_open` is not set.
+    FullyOpenTimespan {
+        input: String,
+    },
+    /// A `<time> ±<duration>` window has a zero or negative radius.
+    EmptyWindow {
+        input: String,
+    },
+    /// The end of a timespan comes before its start.
+    ReversedTimespan {
+        input: String,
+        start: DateTime<FixedOffset>,
+        stop: DateTime<FixedOffset>,
+    },
+    /// A `<first>-<last>` day range is out of order or names invalid
+    /// days.
+    InvalidDayRange {
+        input: String,
+    },
+    /// An `s<seconds>` time of day is past the end of the day, and
+    /// `Parser::roll_over_seconds_of_day` is not set.
+    SecondsOfDayOutOfRange {
+        input: String,
+    },
+    /// An item of a timespan list is empty, as in `a, , b`.
+    EmptyListItem {
+        input: String,
+    },
+    /// A timespan list can be split into timespans in more than one
+    /// way, as in `Mon, 27 Oct 2025`.
+    AmbiguousList {
+        input: String,
+    },
+    /// An ISO 8601 duration with years or months was given where no
+    /// start date is known to measure them from.
+    CalendarDuration {
+        input: String,
+    },
+    /// The input names a leap second (`23:59:60`) and the parser is set
+    /// to `LeapSecond::Reject`.
+    LeapSecond {
+        input: String,
+    },
+    /// Rounding to `step` failed, e.g. because `step` is zero.
+    Rounding {
+        step: Duration,
+        source: chrono::RoundingError,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NoFormatMatched { input, tried } => {
+                write!(f, "Could not parse time string: {:?}", input)?;
+                if !tried.is_empty() {
+                    let tried: Vec<String> = tried.iter().map(|t| format!("{:?}", t)).collect();
+                    write!(f, " (tried {} formats: {})", tried.len(), tried.join(", "))?;
+                }
+                Ok(())
+            }
+            ParseError::OutOfRange { input } => write!(f, "Time out of range: {:?}", input),
+            ParseError::NonexistentLocal { input } => {
+                write!(f, "Nonexistent local time: {:?}", input)
+            }
+            ParseError::AmbiguousLocal {
+                input,
+                earlier,
+                later,
+            } => write!(
+                f,
+                "Ambiguous local time: {:?} (either {} or {})",
+                input, earlier, later
+            ),
+            ParseError::MisplacedOffset { input } => {
+                write!(f, "UTC offset must come last: {:?}", input)
+            }
+            ParseError::InvalidDuration { input } => {
+                write!(f, "Could not parse duration: {:?}", input)
+            }
+            ParseError::InvalidOffset { input } => write!(f, "Could not parse offset: {:?}", input),
+            ParseError::MissingSeparator { input, separator } => {
+                write!(f, "Missing {:?} in timespan: {:?}", separator, input)
+            }
+            ParseError::FullyOpenTimespan { input } => {
+                write!(f, "Timespan has neither start nor end: {:?}", input)
+            }
+            ParseError::EmptyWindow { input } => {
+                write!(f, "Window must have a positive length: {:?}", input)
+            }
+            ParseError::ReversedTimespan { input, start, stop } => write!(
+                f,
+                "Invalid timespan '{}': end time ({}) is before start time ({})",
+                input,
+                stop.format("%Y-%m-%d %H:%M:%S %z"),
+                start.format("%Y-%m-%d %H:%M:%S %z")
+            ),
+            ParseError::InvalidDayRange { input } => write!(f, "Invalid day range: {:?}", input),
+            ParseError::SecondsOfDayOutOfRange { input } => {
+                write!(f, "Seconds since midnight must be below 86400: {:?}", input)
+            }
+            ParseError::EmptyListItem { input } => {
+                write!(f, "Empty timespan in list: {:?}", input)
+            }
+            ParseError::AmbiguousList { input } => {
+                write!(f, "Timespan list can be split in several ways: {:?}", input)
+            }
+            ParseError::CalendarDuration { input } => write!(
+                f,
+                "Years and months have no fixed length: {:?} (use it as the end of a timespan)",
+                input
+            ),
+            ParseError::LeapSecond { input } => {
+                write!(f, "Leap second not supported: {:?}", input)
+            }
+            ParseError::Rounding { step, source } => {
+                write!(f, "Could not round to {}: {}", step, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Rounding { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl ParseError {
+    pub(crate) fn out_of_range(input: &str) -> Self {
+        ParseError::OutOfRange {
+            input: input.to_string(),
+        }
+    }
+}
+
+/// Separate the start and the end of a timespan, tried in order; `..`
+/// is the canonical one.
+const TIMESPAN_SEPARATORS: [&str; 3] = ["..", " to ", "->"];
 
+// Slash-separated dates depend on the locale, see `DateOrder`.
 lazy_static! {
     static ref TIMEPARSER_FORMATS: Vec<&'static str> = vec![
         "%Y-%m-%d",
         "%Y-%m-%d %H:%M",
         "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H",
         "%m-%d",
-        "%m/%d",
         "%m-%d %H:%M:%S",
         "%m-%d %H:%M",
         "%d %H:%M",
         "%d %Hh%M",
         "%d %Hh",
         "%H:%M:%S",
+        "%H:%M:%S%.f",
         "%H:%M",
         "%Hh%M",
         "%Hh",
+        // 12-hour clock, with or without a space before `am`/`pm`
+        "%I %p",
+        "%I:%M %p",
+        "%I:%M:%S %p",
         "%Mm",
         "%M",
+        "%Y-%m",
+        "%Y-%j",
+        "%Y-%jT%H:%M",
+        "%Y-%j %H:%M",
+        "%Y-%jT%H:%M:%S",
+        "%Y-%j %H:%M:%S",
+        // alone, from `MIN_BARE_YEAR` on
+        "%Y",
+        // month names, `%B` reading abbreviations too. `%B %C%y` comes
+        // before `%B %d %Y`, which would read `Jan 2024` as Jan 20, 24,
+        // and takes exactly four digits, so `Feb 30` isn't a year.
+        "%B %d",
+        "%B %C%y",
+        "%B %d %Y",
+        "%B %d, %Y",
+        "%B %d %H:%M",
+        "%d %B",
+        "%d %B %Y",
         "@%s",
+        // `date` output and ISO 8601 with an offset
+        "%a %b %e %H:%M:%S %Y",
+        "%+",
+        // trailing numeric offset (`+0200`, `+02:00`) or `Z`
+        "%Y-%m-%d %H:%M %#z",
+        "%Y-%m-%d %H:%M:%S %#z",
+        "%Y-%m-%dT%H:%M%#z",
+        "%H:%M %#z",
+        "%H:%M:%S %#z",
+        // ISO 8601 basic format
+        "%Y%m%dT%H%M%S",
+        "%Y%m%dT%H%M%S%z",
     ];
+    /// `TIMEPARSER_FORMATS` tokenized once, in the same order.
+    static ref TIMEPARSER_ITEMS: Vec<Vec<chrono::format::Item<'static>>> =
+        TIMEPARSER_FORMATS.iter().map(|f| parse::compile(f)).collect();
+    /// Parser behind `parse_detailed`, whose details borrow its formats.
+    static ref DEFAULT_PARSER: Parser = Parser::new();
 }
 
+/// Parse `timestr`, taking the fields it lacks from `reference`.
+///
+/// Besides the formats, the keywords `now`, `today`, `tomorrow` and
+/// `yesterday` are recognized in any case: `now` is the reference
+/// itself, the others midnight of the reference day, the day after or
+/// the day before, placed in a timezone like any other wall-clock
+/// time.
 pub fn parse_with_reference<Tz: TimeZone>(
     timestr: &str,
     reference: &DateTime<Tz>,
-) -> Result<DateTime<FixedOffset>, String> {
-    if timestr.is_empty() {
-        // XXXvlab: don't know a better way yet to make a
-        // DateTime<FixedOffset> from a DateTime<Local>
-        log::trace!("Using reference: {:?}", reference);
-        return parse::parse_partial("", "", reference, false).map_err(|_| unreachable!());
-    }
-
-    for format in TIMEPARSER_FORMATS.iter() {
-        log::trace!("Trying to parse {:?} with format {:?}", timestr, format);
-        if let Ok(dt) = parse::parse_partial(timestr, format, reference, true) {
-            return Ok(dt);
-        }
-    }
-    Err(format!("Could not parse time string: {:?}", timestr))
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    Parser::new().parse(timestr, reference)
+}
+
+/// Like `parse_with_reference`, but with explicit control over how
+/// missing fields are completed.
+pub fn parse_with_fill<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+    fill: Fill,
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    Parser::new().fill(fill).parse(timestr, reference)
+}
+
+/// Like `parse_with_reference`, but report the wall-clock time, the
+/// offset chosen for it, whether a DST ambiguity was resolved and which
+/// format read the input.
+pub fn parse_detailed<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+) -> Result<ParseDetails<'static>, ParseError> {
+    DEFAULT_PARSER.parse_detailed(timestr, reference)
+}
+
+/// Like `parse_with_reference`, but return seconds since the Unix
+/// epoch, fractional part included.
+///
+/// An `f64` holds 53 significant bits, so precision shrinks as the
+/// timestamp grows: around the present it is about a quarter of a
+/// microsecond, and nanoseconds are lost. Use `DateTime::timestamp` and
+/// `DateTime::timestamp_subsec_nanos` on `parse_with_reference`'s
+/// result when they matter.
+pub fn parse_unix_f64<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+) -> Result<f64, ParseError> {
+    let dt = Parser::new().parse(timestr, reference)?;
+    Ok(dt.timestamp() as f64 + f64::from(dt.timestamp_subsec_nanos()) / 1e9)
+}
+
+/// Like `parse_with_reference`, but never fails or panics; see
+/// `ParseOutcome`.
+pub fn try_parse<Tz: TimeZone>(timestr: &str, reference: &DateTime<Tz>) -> ParseOutcome {
+    Parser::new().try_parse(timestr, reference)
+}
+
+/// Like `parse_with_reference`, but resolve wall-clock times in the
+/// reference's own timezone instead of the system one, so the result
+/// doesn't depend on `TZ`. Use a `chrono_tz::Tz` reference (feature
+/// `chrono-tz`) to get a named zone's DST rules.
+pub fn parse_keep_reference_zone<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+) -> Result<DateTime<Tz>, ParseError> {
+    Parser::new().parse_keep_reference_zone(timestr, reference)
+}
+
+/// Parse `timestr` against several plausible references, keeping the
+/// first unambiguous result, see `Parser::parse_with_references`.
+pub fn parse_with_references<Tz: TimeZone>(
+    timestr: &str,
+    references: &[DateTime<Tz>],
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    Parser::new().parse_with_references(timestr, references)
+}
+
+/// Like `parse_with_reference`, but return the result in the system
+/// timezone. The instant is the same, only the offset it is shown with
+/// may change.
+pub fn parse_with_reference_local<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+) -> Result<DateTime<chrono::Local>, ParseError> {
+    parse_with_reference(timestr, reference).map(|dt| dt.with_timezone(&chrono::Local))
 }
 
-pub fn parse(timespan: &str) -> Result<DateTime<FixedOffset>, String> {
+/// Like `parse_with_reference`, but return the same instant in UTC.
+pub fn parse_with_reference_utc<Tz: TimeZone>(
+    timestr: &str,
+    reference: &DateTime<Tz>,
+) -> Result<DateTime<chrono::Utc>, ParseError> {
+    parse_with_reference(timestr, reference).map(|dt| dt.to_utc())
+}
+
+/// Like `parse`, but return the result in the system timezone, see
+/// `parse_with_reference_local`.
+pub fn parse_local(timestr: &str) -> Result<DateTime<chrono::Local>, ParseError> {
+    parse(timestr).map(|dt| dt.with_timezone(&chrono::Local))
+}
+
+/// Parse the longest valid prefix of `timestr`, ignoring trailing
+/// content such as `2025-10-27 (approx)`. The ignored remainder is
+/// returned alongside the result.
+pub fn parse_loose<'a, Tz: TimeZone>(
+    timestr: &'a str,
+    reference: &DateTime<Tz>,
+) -> Result<(DateTime<FixedOffset>, &'a str), ParseError> {
+    Parser::new().parse_loose(timestr, reference)
+}
+
+/// Parse `timestr` relative to an already resolved `anchor`, typically
+/// the start of a timespan when parsing its end, as timespans do.
+/// Accepts everything `parse_with_reference` does.
+///
+/// Missing fields are taken from the anchor (so `30` keeps the
+/// anchor's date and hour), and the result stays in the anchor's own
+/// offset instead of being re-resolved through the system timezone.
+pub fn parse_relative_to(
+    timestr: &str,
+    anchor: &DateTime<FixedOffset>,
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    Parser::new().parse_relative_to(timestr, anchor)
+}
+
+/// English weekday name or abbreviation (`Friday`, `fri`, `Tues.`),
+/// see `Locale::parse_weekday` for other languages.
+pub fn parse_weekday(s: &str) -> Option<chrono::Weekday> {
+    Locale::ENGLISH.parse_weekday(s)
+}
+
+/// English month name or abbreviation (`October`, `oct`, `Sept.`),
+/// see `Locale::parse_month` for other languages.
+pub fn parse_month(s: &str) -> Option<chrono::Month> {
+    Locale::ENGLISH.parse_month(s)
+}
+
+/// Describe the inputs accepted by the default parser, e.g. for
+/// completion or help screens.
+pub fn supported_syntax() -> SupportedSyntax {
+    Parser::new().supported_syntax()
+}
+
+pub fn parse(timespan: &str) -> Result<DateTime<FixedOffset>, ParseError> {
     let now = chrono::Local::now();
     parse_with_reference(timespan, &now)
 }
 
-pub fn parse_utc(timespan: &str) -> Result<DateTime<FixedOffset>, String> {
+pub fn parse_utc(timespan: &str) -> Result<DateTime<FixedOffset>, ParseError> {
     let now = chrono::Utc::now();
     parse_with_reference(timespan, &now)
 }
 
-type Timespan = (DateTime<FixedOffset>, DateTime<FixedOffset>);
+/// A time read with `parse`, for `str::parse`:
+/// `let t: KalTime = "tomorrow 10:00".parse()?;`. It derefs to, and
+/// converts into, the `DateTime` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KalTime(pub DateTime<FixedOffset>);
+
+impl FromStr for KalTime {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse(s).map(KalTime)
+    }
+}
+
+impl Deref for KalTime {
+    type Target = DateTime<FixedOffset>;
+
+    fn deref(&self) -> &DateTime<FixedOffset> {
+        &self.0
+    }
+}
+
+impl From<DateTime<FixedOffset>> for KalTime {
+    fn from(dt: DateTime<FixedOffset>) -> Self {
+        KalTime(dt)
+    }
+}
+
+impl From<KalTime> for DateTime<FixedOffset> {
+    fn from(time: KalTime) -> Self {
+        time.0
+    }
+}
+
+impl fmt::Display for KalTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+type TimespanBounds = (DateTime<FixedOffset>, DateTime<FixedOffset>);
+/// A timespan whose missing bounds are unbounded.
+type OpenTimespan = (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>);
 
 pub fn parse_timespan_with_reference<Tz: TimeZone>(
     timespan: &str,
     default: &DateTime<Tz>,
-) -> Result<Timespan, String> {
-    let (start, stop) = match timespan.split_once("..") {
-        Some((start, stop)) => {
-            let first = parse_with_reference(start, default)?;
-            let second = parse_with_reference(stop, &first)?;
-            (first, second)
-        }
-        None => {
-            let start = parse_with_reference(timespan, default)?;
-            let stop = start + chrono::Duration::days(1);
-            (start, stop)
-        }
-    };
-
-    // Validate that start <= stop (reject reverse timespans)
-    if start > stop {
-        return Err(format!(
-            "Invalid timespan '{}': end time ({}) is before start time ({})",
-            timespan,
-            stop.format("%Y-%m-%d %H:%M:%S %z"),
-            start.format("%Y-%m-%d %H:%M:%S %z")
-        ));
-    }
+) -> Result<TimespanBounds, ParseError> {
+    Parser::new().parse_timespan(timespan, default)
+}
 
-    Ok((start, stop))
+/// Like `parse_timespan_with_reference`, but either side of the
+/// separator may be left empty for an unbounded one (`2025-01-01..`,
+/// `..2025-01-01`), but not both; see `Parser::parse_timespan_open`.
+pub fn parse_timespan_open<Tz: TimeZone>(
+    timespan: &str,
+    reference: &DateTime<Tz>,
+) -> Result<OpenTimespan, ParseError> {
+    Parser::new().parse_timespan_open(timespan, reference)
 }
 
-pub fn parse_timespan(timespan: &str) -> Result<Timespan, String> {
+/// Parse a list of timespans separated by `,`, `&` or `and`, see
+/// `Parser::parse_timespan_list`.
+pub fn parse_timespan_list<Tz: TimeZone>(
+    input: &str,
+    reference: &DateTime<Tz>,
+) -> Result<Vec<TimespanBounds>, ParseError> {
+    Parser::new().parse_timespan_list(input, reference)
+}
+
+/// A single time or a timespan, see `parse_flexible`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOrSpan {
+    Point(DateTime<FixedOffset>),
+    Span(TimespanBounds),
+}
+
+/// Parse `input` as a timespan if it contains a separator, and as a
+/// single time otherwise (instead of the one-day span
+/// `parse_timespan_with_reference` would give).
+pub fn parse_flexible<Tz: TimeZone>(
+    input: &str,
+    reference: &DateTime<Tz>,
+) -> Result<TimeOrSpan, ParseError> {
+    let parser = Parser::new();
+    if parser.split_timespan(input).is_some() {
+        parser
+            .parse_timespan(input, reference)
+            .map(TimeOrSpan::Span)
+    } else {
+        parser.parse(input, reference).map(TimeOrSpan::Point)
+    }
+}
+
+pub fn parse_timespan(timespan: &str) -> Result<TimespanBounds, ParseError> {
     let now = chrono::Local::now();
     parse_timespan_with_reference(timespan, &now)
 }
@@ -95,7 +542,7 @@ pub fn parse_timespan(timespan: &str) -> Result<Timespan, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use chrono::{Timelike, Utc};
 
     fn pp<Tz: TimeZone>(s: &str, dt: &DateTime<Tz>) -> String {
         format!("{:?}", parse_with_reference(s, dt))
@@ -111,19 +558,242 @@ mod tests {
         assert_eq!(pp("30m", &dt), "Ok(2014-07-08T09:30:00+00:00)");
     }
 
+    #[test]
+    fn test_fill_reference() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+        let pf = |s| format!("{:?}", parse_with_fill(s, &dt, Fill::Reference));
+
+        assert_eq!(pf(""), "Ok(2014-07-08T09:10:11+00:00)");
+        assert_eq!(pf("2015"), "Ok(2015-07-08T09:10:11+00:00)");
+        assert_eq!(pf("2015-02"), "Ok(2015-02-08T09:10:11+00:00)");
+        assert_eq!(pf("2015-02-01"), "Ok(2015-02-01T09:10:11+00:00)");
+        assert_eq!(pf("2015-02-01 23"), "Ok(2015-02-01T23:10:11+00:00)");
+        assert_eq!(pf("2015-02-01 23:22"), "Ok(2015-02-01T23:22:11+00:00)");
+        assert_eq!(pf("2015-02-01 23:22:12"), "Ok(2015-02-01T23:22:12+00:00)");
+    }
+
+    #[test]
+    fn test_fill_reference_nanoseconds() {
+        let dt = Utc
+            .with_ymd_and_hms(2014, 7, 8, 9, 10, 11)
+            .unwrap()
+            .with_nanosecond(123_456_789)
+            .unwrap();
+        let pf = |s, fill| format!("{:?}", parse_with_fill(s, &dt, fill));
+
+        assert_eq!(
+            pf("2015", Fill::Reference),
+            "Ok(2015-07-08T09:10:11.123456789+00:00)"
+        );
+        assert_eq!(
+            pf("2015-02-01 23:22:12", Fill::Reference),
+            "Ok(2015-02-01T23:22:12.123456789+00:00)"
+        );
+        assert_eq!(pf("2015", Fill::Zeroes), "Ok(2015-01-01T00:00:00+00:00)");
+    }
+
+    #[test]
+    fn test_fill_modes() {
+        // the examples of `Fill`
+        let dt = Utc
+            .with_ymd_and_hms(2014, 7, 8, 9, 10, 11)
+            .unwrap()
+            .with_nanosecond(500_000_000)
+            .unwrap();
+        let pf = |s, fill| {
+            parse_with_fill(s, &dt, fill).map(|d| d.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+        };
+
+        for (input, zeroes, reference) in [
+            ("2015", "2015-01-01 00:00:00", "2015-07-08 09:10:11.500"),
+            ("Jan 5", "2014-01-05 00:00:00", "2014-01-05 09:10:11.500"),
+            ("10:15", "2014-07-08 10:15:00", "2014-07-08 10:15:11.500"),
+            (
+                "2015-02-01 23:22:12",
+                "2015-02-01 23:22:12",
+                "2015-02-01 23:22:12.500",
+            ),
+        ] {
+            assert_eq!(pf(input, Fill::Zeroes).as_deref(), Ok(zeroes), "{input}");
+            assert_eq!(
+                pf(input, Fill::Reference).as_deref(),
+                Ok(reference),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_zeroes_year_only() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+
+        assert_eq!(pp("2015", &dt), "Ok(2015-01-01T00:00:00+00:00)");
+        assert_eq!(pp("2015-02", &dt), "Ok(2015-02-01T00:00:00+00:00)");
+        assert_eq!(pp("30", &dt), "Ok(2014-07-08T09:30:00+00:00)");
+    }
+
+    #[test]
+    fn test_clock_like_numbers_are_not_years() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+
+        for input in ["930", "0930", "1015", "1859"] {
+            assert!(parse_with_reference(input, &dt).is_err(), "{input}");
+            assert!(
+                parse_with_fill(input, &dt, Fill::Reference).is_err(),
+                "{input}"
+            );
+            // at most a prefix is read, as minutes
+            if let Ok((parsed, _)) = Parser::new().parse_loose(input, &dt) {
+                assert_eq!(parsed.date_naive(), dt.date_naive(), "{input}");
+            }
+        }
+        assert_eq!(pp("1900", &dt), "Ok(1900-01-01T00:00:00+00:00)");
+        // custom formats read them as asked
+        assert_eq!(
+            Parser::new()
+                .with_formats(&["%Y"])
+                .parse("1015", &dt)
+                .map(|dt| dt.to_rfc3339()),
+            Ok("1015-01-01T00:00:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ordinal_date() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+
+        assert_eq!(pp("2025-300", &dt), "Ok(2025-10-27T00:00:00+00:00)");
+        assert_eq!(pp("2025-300T10:15", &dt), "Ok(2025-10-27T10:15:00+00:00)");
+        assert_eq!(pp("2025-300 10:15", &dt), "Ok(2025-10-27T10:15:00+00:00)");
+        assert_eq!(
+            pp("2025-300T10:15:30", &dt),
+            "Ok(2025-10-27T10:15:30+00:00)"
+        );
+        assert_eq!(pp("2024-366T23:59", &dt), "Ok(2024-12-31T23:59:00+00:00)");
+        assert!(parse_with_reference("2025-366T23:59", &dt).is_err());
+        // month-based dates still win
+        assert_eq!(pp("2025-10", &dt), "Ok(2025-10-01T00:00:00+00:00)");
+    }
+
+    #[test]
+    fn test_month_names() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+        let pf = |s| format!("{:?}", parse_with_fill(s, &dt, Fill::Reference));
+
+        assert_eq!(pp("Jan 5", &dt), "Ok(2014-01-05T00:00:00+00:00)");
+        assert_eq!(pp("january 5", &dt), "Ok(2014-01-05T00:00:00+00:00)");
+        assert_eq!(pp("5 Mar 2024", &dt), "Ok(2024-03-05T00:00:00+00:00)");
+        assert_eq!(pp("5 MARCH", &dt), "Ok(2014-03-05T00:00:00+00:00)");
+        assert_eq!(pp("March 2025", &dt), "Ok(2025-03-01T00:00:00+00:00)");
+        assert_eq!(pp("Jan 2024", &dt), "Ok(2024-01-01T00:00:00+00:00)");
+        assert_eq!(pp("March 1925", &dt), "Ok(1925-03-01T00:00:00+00:00)");
+        assert_eq!(pp("Jan 20 2024", &dt), "Ok(2024-01-20T00:00:00+00:00)");
+        assert_eq!(pp("Jan 5, 2024", &dt), "Ok(2024-01-05T00:00:00+00:00)");
+        assert_eq!(pp("Dec 24 18:30", &dt), "Ok(2014-12-24T18:30:00+00:00)");
+        assert!(pp("Feb 30", &dt).starts_with("Err"));
+
+        assert_eq!(pf("Jan 5"), "Ok(2014-01-05T09:10:11+00:00)");
+        assert_eq!(pf("March 2025"), "Ok(2025-03-08T09:10:11+00:00)");
+        assert_eq!(pf("Dec 24 18:30"), "Ok(2014-12-24T18:30:11+00:00)");
+    }
+
+    #[test]
+    fn test_twelve_hour_clock() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
+
+        assert_eq!(pp("12am", &dt), "Ok(2014-07-08T00:00:00+00:00)");
+        assert_eq!(pp("12pm", &dt), "Ok(2014-07-08T12:00:00+00:00)");
+        assert_eq!(pp("3:30pm", &dt), "Ok(2014-07-08T15:30:00+00:00)");
+        assert_eq!(pp("3:30 PM", &dt), "Ok(2014-07-08T15:30:00+00:00)");
+        assert_eq!(pp("11:59:30 am", &dt), "Ok(2014-07-08T11:59:30+00:00)");
+        assert_eq!(pp("7 AM", &dt), "Ok(2014-07-08T07:00:00+00:00)");
+        assert_eq!(pp("tomorrow 3pm", &dt), "Ok(2014-07-09T15:00:00+00:00)");
+        assert!(pp("13pm", &dt).starts_with("Err"));
+        assert!(pp("0am", &dt).starts_with("Err"));
+
+        assert_eq!(
+            format!("{:?}", parse_with_fill("3pm", &dt, Fill::Reference)),
+            "Ok(2014-07-08T15:10:11+00:00)"
+        );
+    }
+
+    #[test]
+    fn test_fractional_seconds() {
+        let dt = Utc
+            .with_ymd_and_hms(2014, 7, 8, 9, 10, 11)
+            .unwrap()
+            .with_nanosecond(987_654_321)
+            .unwrap();
+        let nanos = |s| parse_with_reference(s, &dt).map(|d| d.nanosecond());
+
+        assert_eq!(pp("12:00:00.250", &dt), "Ok(2014-07-08T12:00:00.250+00:00)");
+        assert_eq!(nanos("12:00:00.250"), Ok(250_000_000));
+        assert_eq!(
+            pp("2025-01-01 12:00:00.123456", &dt),
+            "Ok(2025-01-01T12:00:00.123456+00:00)"
+        );
+        assert_eq!(nanos("2025-01-01 12:00:00.123456"), Ok(123_456_000));
+        assert_eq!(nanos("2025-01-01T12:00:00.000000001"), Ok(1));
+        // explicit fractions win over the reference's, whatever the fill
+        assert_eq!(
+            parse_with_fill("12:00:00.5", &dt, Fill::Reference).map(|d| d.nanosecond()),
+            Ok(500_000_000)
+        );
+        assert_eq!(nanos("12:00:00"), Ok(0));
+        assert!(pp("12:00:00.", &dt).starts_with("Err"));
+    }
+
     #[test]
     fn test_ts() {
         let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(); // `2014-07-08T09:10:11Z`
         assert_eq!(pp("@1704150000", &dt), "Ok(2024-01-01T23:00:00+00:00)");
     }
 
+    #[test]
+    fn test_ctime_and_iso_offset() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        assert_eq!(
+            pp("Mon Oct 27 10:15:00 2025", &dt),
+            "Ok(2025-10-27T10:15:00+00:00)"
+        );
+        assert_eq!(
+            pp("Wed Oct  1 08:00:00 2025", &dt),
+            "Ok(2025-10-01T08:00:00+00:00)"
+        );
+        // the weekday must match the date
+        assert!(pp("Tue Oct 27 10:15:00 2025", &dt).starts_with("Err"));
+
+        assert_eq!(
+            pp("2025-10-27T10:15:00+02:00", &dt),
+            "Ok(2025-10-27T10:15:00+02:00)"
+        );
+        assert_eq!(
+            pp("2025-10-27T10:15:00.250Z", &dt),
+            "Ok(2025-10-27T10:15:00.250+00:00)"
+        );
+    }
+
+    #[test]
+    fn test_iso_basic_format() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        assert_eq!(
+            pp("20251027T101500+0200", &dt),
+            "Ok(2025-10-27T10:15:00+02:00)"
+        );
+        assert_eq!(
+            pp("20251027T101500-0530", &dt),
+            "Ok(2025-10-27T10:15:00-05:30)"
+        );
+        assert_eq!(pp("20251027T101500", &dt), "Ok(2025-10-27T10:15:00+00:00)");
+    }
+
     #[test]
     fn test_timespan_end_uses_start_for_missing_fields() {
         let reference = Utc.with_ymd_and_hms(2025, 10, 27, 6, 0, 0).unwrap();
         let offset = FixedOffset::east_opt(0).unwrap();
 
-        let (start, stop) = super::parse_timespan_with_reference("10:15..30", &reference)
-            .expect("timespan parse");
+        let (start, stop) =
+            super::parse_timespan_with_reference("10:15..30", &reference).expect("timespan parse");
 
         let expected_start = offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap();
         let expected_stop = offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 0).unwrap();
@@ -132,16 +802,205 @@ mod tests {
         assert_eq!(stop, expected_stop);
     }
 
+    #[test]
+    fn test_timespan_hour_only_end() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 27, 6, 0, 0).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        let (start, stop) =
+            super::parse_timespan_with_reference("10:30..11", &reference).expect("timespan parse");
+        assert_eq!(
+            start,
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 0).unwrap()
+        );
+        assert_eq!(
+            stop,
+            offset.with_ymd_and_hms(2025, 10, 27, 11, 0, 0).unwrap()
+        );
+
+        let (_, stop) = super::parse_timespan_with_reference("2025-10-20 10:30:45..11", &reference)
+            .expect("timespan parse");
+        assert_eq!(
+            stop,
+            offset.with_ymd_and_hms(2025, 10, 20, 11, 0, 0).unwrap()
+        );
+
+        // still a minute when it doesn't go backwards
+        let (_, stop) =
+            super::parse_timespan_with_reference("10:05..11", &reference).expect("timespan parse");
+        assert_eq!(
+            stop,
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 11, 0).unwrap()
+        );
+
+        assert!(super::parse_timespan_with_reference("10:30..9", &reference).is_err());
+    }
+
+    #[test]
+    fn test_timespan_seconds_only_end() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 27, 6, 0, 0).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        let (start, stop) = super::parse_timespan_with_reference("10:30:45..:50", &reference)
+            .expect("timespan parse");
+        assert_eq!(
+            start,
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 45).unwrap()
+        );
+        assert_eq!(
+            stop,
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 50).unwrap()
+        );
+
+        let (_, stop) =
+            super::parse_timespan_with_reference("2025-10-20 10:30:45..:50", &reference)
+                .expect("timespan parse");
+        assert_eq!(
+            stop,
+            offset.with_ymd_and_hms(2025, 10, 20, 10, 30, 50).unwrap()
+        );
+
+        assert!(super::parse_timespan_with_reference("10:30:45..:40", &reference).is_err());
+        assert!(super::parse_timespan_with_reference("10:30:45..:60", &reference).is_err());
+    }
+
+    #[test]
+    fn test_kal_time_from_str() {
+        let time: KalTime = "2025-01-01 10:00 +02:00".parse().unwrap();
+        assert_eq!(time.to_string(), "2025-01-01 10:00:00 +02:00");
+        assert_eq!(time.hour(), 10);
+        let dt: DateTime<FixedOffset> = time.into();
+        assert_eq!(dt, parse("2025-01-01 10:00 +02:00").unwrap());
+
+        assert_eq!(
+            "nonsense".parse::<KalTime>(),
+            Err(ParseError::NoFormatMatched {
+                input: "nonsense".to_string(),
+                tried: Vec::new()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_flexible() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 1, 6, 0, 0).unwrap();
+        let day = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 27, 0, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            parse_flexible("2025-10-27", &reference),
+            Ok(TimeOrSpan::Point(day))
+        );
+        assert_eq!(
+            parse_flexible("2025-10-27..", &reference),
+            Ok(TimeOrSpan::Span((day, day)))
+        );
+        assert_eq!(
+            parse_flexible("2025-10-27..2025-10-28", &reference),
+            Ok(TimeOrSpan::Span((day, day + chrono::Duration::days(1))))
+        );
+        for input in ["2025-10-27 TO 2025-10-28", "2025-10-27\tto 2025-10-28"] {
+            assert_eq!(
+                parse_flexible(input, &reference),
+                Ok(TimeOrSpan::Span((day, day + chrono::Duration::days(1)))),
+                "{input:?}"
+            );
+        }
+        assert!(parse_flexible("toto", &reference).is_err());
+    }
+
+    #[test]
+    fn test_local_and_utc_results() {
+        let reference = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 27, 6, 0, 0)
+            .unwrap();
+        let input = "2025-10-27 09:10:11 +02:00";
+        let fixed = super::parse_with_reference(input, &reference).unwrap();
+
+        let utc = super::parse_with_reference_utc(input, &reference).unwrap();
+        assert_eq!(utc, Utc.with_ymd_and_hms(2025, 10, 27, 7, 10, 11).unwrap());
+        assert_eq!(utc, fixed);
+
+        let local = super::parse_with_reference_local(input, &reference).unwrap();
+        assert_eq!(local, fixed);
+        assert_eq!(local, fixed.with_timezone(&chrono::Local));
+
+        assert!(super::parse_local("garbage").is_err());
+        assert_eq!(
+            super::parse_local("@1761556211").map(|dt| dt.timestamp()),
+            Ok(1761556211)
+        );
+    }
+
+    #[test]
+    fn test_parse_unix_f64() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 27, 6, 0, 0).unwrap();
+
+        assert_eq!(
+            super::parse_unix_f64("@1761556211.25", &reference),
+            Ok(1761556211.25)
+        );
+        assert_eq!(
+            super::parse_unix_f64("2025-10-27 09:10:11.5 +00:00", &reference),
+            Ok(1761556211.5)
+        );
+        assert_eq!(super::parse_unix_f64("@-1.5", &reference), Ok(-1.5));
+        let micros = super::parse_unix_f64("@1761556211.123456", &reference).unwrap();
+        assert!((micros - 1761556211.123456).abs() < 1e-6);
+        assert!(super::parse_unix_f64("garbage", &reference).is_err());
+    }
+
+    #[test]
+    fn test_structured_errors() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let at = |h| {
+            Utc.with_ymd_and_hms(2025, 10, 27, h, 0, 0)
+                .unwrap()
+                .fixed_offset()
+        };
+
+        let err = parse_timespan_with_reference("14:00..10:00", &dt).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::ReversedTimespan {
+                input: "14:00..10:00".to_string(),
+                start: at(14),
+                stop: at(10),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid timespan '14:00..10:00': end time (2025-10-27 10:00:00 +0000) \
+             is before start time (2025-10-27 14:00:00 +0000)"
+        );
+
+        assert!(matches!(
+            parse_with_reference("toto", &dt),
+            Err(ParseError::NoFormatMatched { .. })
+        ));
+        assert_eq!(
+            parse_duration("1x").map_err(|e| e.to_string()),
+            Err("Could not parse duration: \"1x\"".to_string())
+        );
+        let err = round_to(
+            &dt.fixed_offset(),
+            chrono::Duration::zero(),
+            Rounding::Floor,
+        );
+        assert!(std::error::Error::source(&err.unwrap_err()).is_some());
+    }
+
     #[test]
     fn test_timespan_full_start_keeps_end_on_same_day() {
         let reference = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
         let offset = FixedOffset::east_opt(0).unwrap();
 
-        let (start, stop) = super::parse_timespan_with_reference(
-            "2025-10-27 10:30..11:30",
-            &reference,
-        )
-        .expect("timespan parse");
+        let (start, stop) =
+            super::parse_timespan_with_reference("2025-10-27 10:30..11:30", &reference)
+                .expect("timespan parse");
 
         let expected_start = offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 0).unwrap();
         let expected_stop = offset.with_ymd_and_hms(2025, 10, 27, 11, 30, 0).unwrap();
@@ -150,10 +1009,148 @@ mod tests {
         assert_eq!(stop, expected_stop);
     }
 
+    #[test]
+    fn test_parse_relative_to_inherits_anchor_date() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let anchor = offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap();
+
+        let end = parse_relative_to("30", &anchor).expect("relative parse");
+        assert_eq!(
+            end,
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 30, 0).unwrap()
+        );
+
+        let end = parse_relative_to("23:45", &anchor).expect("relative parse");
+        assert_eq!(
+            end,
+            offset.with_ymd_and_hms(2025, 10, 27, 23, 45, 0).unwrap()
+        );
+
+        assert_eq!(parse_relative_to("", &anchor), Ok(anchor));
+    }
+
+    #[test]
+    fn test_parse_relative_to_chains_timespan_bounds() {
+        let reference = Utc.with_ymd_and_hms(2025, 10, 27, 6, 0, 0).unwrap();
+
+        let start = parse_with_reference("10:15", &reference).expect("start parse");
+        let stop = parse_relative_to("30", &start).expect("stop parse");
+
+        assert_eq!(
+            (start, stop),
+            parse_timespan_with_reference("10:15..30", &reference).expect("timespan parse")
+        );
+    }
+
+    #[test]
+    fn test_anchor_keywords_across_dst() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        // Paris leaves DST on that day, at 03:00 +02:00
+        let reference = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 26, 12, 0, 0)
+            .unwrap();
+        let parse = |s| parse_with_reference(s, &reference).map(|d| d.to_rfc3339());
+
+        assert_eq!(parse("NOW"), Ok(reference.to_rfc3339()));
+        assert_eq!(parse("today"), Ok("2025-10-26T00:00:00+02:00".to_string()));
+        assert_eq!(
+            parse("Tomorrow"),
+            Ok("2025-10-27T00:00:00+01:00".to_string())
+        );
+        assert_eq!(
+            parse("yesterday"),
+            Ok("2025-10-25T00:00:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("someday").map_err(|e| e.to_string()),
+            Err("Could not parse time string: \"someday\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_detailed_reports_format() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let format = |s| parse_detailed(s, &dt).map(|details| details.format);
+
+        assert_eq!(format("2025-10-27"), Ok(Some("%Y-%m-%d")));
+        assert_eq!(format("2025-10-27 10:15:00"), Ok(Some("%Y-%m-%d %H:%M:%S")));
+        assert_eq!(format("10:15"), Ok(Some("%H:%M")));
+        assert_eq!(format("Jan 5"), Ok(Some("%B %d")));
+        assert_eq!(format("@1704150000"), Ok(Some("@%s")));
+        assert_eq!(format("tomorrow 10:15"), Ok(Some("%H:%M")));
+        assert_eq!(format("tomorrow"), Ok(None));
+        assert_eq!(format("+1h"), Ok(None));
+        assert!(TIMEPARSER_FORMATS.contains(&"%B %d"));
+    }
+
+    #[test]
+    fn test_parse_detailed_reports_dst_fold() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let reference = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 12, 1, 12, 0, 0)
+            .unwrap();
+
+        let details = parse_detailed("2025-10-26 02:30", &reference).expect("parse");
+        assert!(details.disambiguated());
+        assert_eq!(details.wall_clock.to_string(), "2025-10-26 02:30:00");
+
+        let details = parse_detailed("2025-10-26 12:30", &reference).expect("parse");
+        assert!(!details.disambiguated());
+        assert_eq!(details.offset, *reference.offset());
+    }
+
+    #[test]
+    fn test_nonexistent_local_time_is_an_error() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let reference = chrono::FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 1, 12, 0, 0)
+            .unwrap();
+
+        // skipped when Paris moves to summer time
+        assert_eq!(
+            super::parse_with_reference("2025-03-30 02:30", &reference),
+            Err(ParseError::NonexistentLocal {
+                input: "2025-03-30 02:30".to_string()
+            })
+        );
+        assert!(
+            super::parse_timespan_with_reference("2025-03-30 02:30..04:00", &reference).is_err()
+        );
+
+        // beyond the representable range once the offset is applied
+        let anchor = chrono::FixedOffset::west_opt(10 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 27, 12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            super::parse_relative_to("+262142-12-31 23:59", &anchor),
+            Err(ParseError::OutOfRange {
+                input: "+262142-12-31 23:59".to_string()
+            })
+        );
+        assert_eq!(
+            super::parse_relative_to("2025-10-28 10:00", &anchor),
+            Ok(anchor
+                .timezone()
+                .with_ymd_and_hms(2025, 10, 28, 10, 0, 0)
+                .unwrap())
+        );
+    }
+
     #[test]
     fn test_full_datetime_should_ignore_reference_offset() {
-        // Demonstrate bug: a fully specified local datetime string parses differently
-        // when the reference has different offsets (winter vs summer).
+        // A fully specified local datetime is placed in the system timezone
+        // at its own date, whatever offset the reference has (only a UTC
+        // reference switches to UTC, see `parse::ResolutionZone`).
         unsafe {
             std::env::set_var("TZ", "Europe/Paris");
         }
@@ -176,5 +1173,57 @@ mod tests {
             "Parsed times should be equal regardless of reference offset, got {} vs {}",
             a, b
         );
+        assert_eq!(a.offset().local_minus_utc(), 7200);
+
+        for east in [-5 * 3600, 9 * 3600, 1800] {
+            let reference = chrono::FixedOffset::east_opt(east)
+                .unwrap()
+                .with_ymd_and_hms(2025, 12, 1, 12, 0, 0)
+                .unwrap();
+            assert_eq!(super::parse_with_reference(s, &reference), Ok(a));
+        }
+    }
+
+    #[test]
+    fn test_offset_in_input_ignores_reference() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let expected = chrono::FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 22, 3, 17, 0)
+            .unwrap();
+        let utc = chrono::Utc
+            .with_ymd_and_hms(2025, 10, 22, 3, 17, 0)
+            .unwrap()
+            .fixed_offset();
+
+        for east in [0, -5 * 3600, 9 * 3600] {
+            let reference = chrono::FixedOffset::east_opt(east)
+                .unwrap()
+                .with_ymd_and_hms(2025, 12, 1, 12, 0, 0)
+                .unwrap();
+            for s in [
+                "2025-10-22 03:17 +0200",
+                "2025-10-22 03:17 +02:00",
+                "2025-10-22 03:17:00 +02:00",
+                "2025-10-22T03:17+02:00",
+            ] {
+                let dt = super::parse_with_reference(s, &reference).expect(s);
+                assert_eq!(dt, expected, "{s}");
+                assert_eq!(dt.offset(), expected.offset(), "{s}");
+            }
+            let dt = super::parse_with_reference("2025-10-22 03:17Z", &reference);
+            assert_eq!(
+                dt.map(|dt| (dt, dt.offset().local_minus_utc())),
+                Ok((utc, 0))
+            );
+        }
+
+        let reference = chrono::Utc.with_ymd_and_hms(2025, 10, 22, 9, 0, 0).unwrap();
+        assert_eq!(
+            super::parse_with_reference("15:00 -0500", &reference).map(|dt| dt.to_string()),
+            Ok("2025-10-22 15:00:00 -05:00".to_string())
+        );
     }
 }
@@ -0,0 +1,274 @@
+use std::borrow::Cow;
+
+use chrono::format::Item;
+use chrono::{Month, Weekday};
+use lazy_static::lazy_static;
+
+use crate::parse;
+
+/// Order of the day, month and year fields in slash-separated dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `2025/10/27`
+    Ymd,
+    /// `27/10/2025`, `27/10/25`, `27/10`
+    Dmy,
+    /// `10/27`, `10/27/25`
+    Mdy,
+}
+
+impl DateOrder {
+    /// Formats implementing this order, tried after the built-in ones.
+    pub(crate) fn formats(self) -> &'static [&'static str] {
+        match self {
+            DateOrder::Ymd => &["%Y/%m/%d", "%Y/%m/%d %H:%M", "%Y/%m/%d %H:%M:%S"],
+            DateOrder::Dmy => &[
+                "%d/%m/%y",
+                "%d/%m/%Y",
+                "%d/%m/%Y %H:%M",
+                "%d/%m/%Y %H:%M:%S",
+                "%d/%m",
+            ],
+            DateOrder::Mdy => &["%m/%d", "%m/%d/%y"],
+        }
+    }
+
+    /// `formats`, tokenized once.
+    pub(crate) fn items(self) -> &'static [Vec<Item<'static>>] {
+        lazy_static! {
+            static ref COMPILED: [Vec<Vec<Item<'static>>>; 3] =
+                [DateOrder::Ymd, DateOrder::Dmy, DateOrder::Mdy].map(|order| order
+                    .formats()
+                    .iter()
+                    .map(|f| parse::compile(f))
+                    .collect());
+        }
+        &COMPILED[self as usize]
+    }
+}
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const ENGLISH_WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// Language conventions used to read an input.
+///
+/// Names are translated to their English equivalent before parsing, so
+/// the rest of the parser only deals with English. A name matches when
+/// it is written in full or shortened to an unambiguous prefix of at
+/// least three letters (`oct`, `sept.`), in any case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    /// Month names, January first.
+    pub months: [&'static str; 12],
+    /// Weekday names, Monday first.
+    pub weekdays: [&'static str; 7],
+    /// Morning and afternoon markers, if the language uses a 12-hour
+    /// clock.
+    pub am_pm: Option<[&'static str; 2]>,
+    pub decimal_separator: char,
+    pub date_order: DateOrder,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::ENGLISH
+    }
+}
+
+impl Locale {
+    /// English names with ISO dates; `10/27` is read month first.
+    pub const ENGLISH: Locale = Locale {
+        months: [
+            "january",
+            "february",
+            "march",
+            "april",
+            "may",
+            "june",
+            "july",
+            "august",
+            "september",
+            "october",
+            "november",
+            "december",
+        ],
+        weekdays: [
+            "monday",
+            "tuesday",
+            "wednesday",
+            "thursday",
+            "friday",
+            "saturday",
+            "sunday",
+        ],
+        am_pm: Some(["am", "pm"]),
+        decimal_separator: '.',
+        date_order: DateOrder::Mdy,
+    };
+
+    pub const FRENCH: Locale = Locale {
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        am_pm: None,
+        decimal_separator: ',',
+        date_order: DateOrder::Dmy,
+    };
+
+    /// Look up a locale by its POSIX-style name (`fr_FR`, `en`, ...).
+    pub fn from_name(name: &str) -> Option<Locale> {
+        let language = name.split(['_', '-', '.']).next()?;
+        match language.to_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Locale::ENGLISH),
+            "fr" => Some(Locale::FRENCH),
+            _ => None,
+        }
+    }
+
+    /// Translate `s` into the English, `.`-decimal form the formats
+    /// expect.
+    pub(crate) fn normalize<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if *self == Locale::ENGLISH {
+            return Cow::Borrowed(s);
+        }
+
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.char_indices().peekable();
+        let mut prev: Option<char> = None;
+        while let Some((i, c)) = chars.next() {
+            if c.is_alphabetic() {
+                let mut end = s.len();
+                while let Some(&(j, c)) = chars.peek() {
+                    if !(c.is_alphabetic() || c == '.') {
+                        end = j;
+                        break;
+                    }
+                    chars.next();
+                }
+                let word = &s[i..end];
+                out.push_str(self.translate(word).unwrap_or(word));
+                prev = word.chars().last();
+                continue;
+            }
+            let next_is_digit = chars.peek().is_some_and(|(_, c)| c.is_ascii_digit());
+            if c == self.decimal_separator
+                && prev.is_some_and(|p| p.is_ascii_digit())
+                && next_is_digit
+            {
+                out.push('.');
+            } else {
+                out.push(c);
+            }
+            prev = Some(c);
+        }
+        log::trace!("Normalized {:?} to {:?}", s, out);
+        Cow::Owned(out)
+    }
+
+    /// Weekday named by `s`, in full or abbreviated.
+    pub fn parse_weekday(&self, s: &str) -> Option<Weekday> {
+        let i = name_index(&self.weekdays, s.trim())?;
+        Weekday::try_from(i as u8).ok()
+    }
+
+    /// Month named by `s`, in full or abbreviated.
+    pub fn parse_month(&self, s: &str) -> Option<Month> {
+        let i = name_index(&self.months, s.trim())?;
+        Month::try_from(i as u8 + 1).ok()
+    }
+
+    fn translate(&self, word: &str) -> Option<&'static str> {
+        if let Some(i) = name_index(&self.months, word) {
+            return Some(ENGLISH_MONTHS[i]);
+        }
+        if let Some(i) = name_index(&self.weekdays, word) {
+            return Some(ENGLISH_WEEKDAYS[i]);
+        }
+        let [am, pm] = self.am_pm?;
+        match word.trim_end_matches('.').to_lowercase() {
+            w if w == am => Some("am"),
+            w if w == pm => Some("pm"),
+            _ => None,
+        }
+    }
+}
+
+// Index of the name `word` stands for, ignoring case and a trailing
+// abbreviation dot.
+fn name_index(names: &[&str], word: &str) -> Option<usize> {
+    let word = word.trim_end_matches('.').to_lowercase();
+    if word.chars().count() < 3 {
+        return None;
+    }
+    unique_prefix_match(names, &word)
+}
+
+// Index of the only name `word` is a prefix of (or equal to).
+fn unique_prefix_match(names: &[&str], word: &str) -> Option<usize> {
+    if let Some(i) = names.iter().position(|n| *n == word) {
+        return Some(i);
+    }
+    let mut matches = names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.starts_with(word));
+    match (matches.next(), matches.next()) {
+        (Some((i, _)), None) => Some(i),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_french_normalize() {
+        let fr = Locale::FRENCH;
+        assert_eq!(fr.normalize("27 octobre 2025"), "27 oct 2025");
+        assert_eq!(fr.normalize("Vendredi 10:15"), "fri 10:15");
+        assert_eq!(fr.normalize("3 Févr."), "3 feb");
+        assert_eq!(fr.normalize("1 jui"), "1 jui");
+        assert_eq!(fr.normalize("10:15:30,5"), "10:15:30.5");
+        assert_eq!(fr.normalize("1, 2"), "1, 2");
+    }
+
+    #[test]
+    fn test_parse_names() {
+        let en = Locale::ENGLISH;
+        assert_eq!(en.parse_weekday("Friday"), Some(Weekday::Fri));
+        assert_eq!(en.parse_weekday("tue"), Some(Weekday::Tue));
+        assert_eq!(en.parse_weekday("tues."), Some(Weekday::Tue));
+        assert_eq!(en.parse_month("September"), Some(Month::September));
+        assert_eq!(en.parse_month("oct"), Some(Month::October));
+        assert_eq!(en.parse_month("ju"), None);
+        assert_eq!(en.parse_month("juillet"), None);
+        assert_eq!(en.parse_weekday("toto"), None);
+
+        assert_eq!(Locale::FRENCH.parse_month("juillet"), Some(Month::July));
+        assert_eq!(Locale::FRENCH.parse_weekday("dim."), Some(Weekday::Sun));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Locale::from_name("fr_FR.UTF-8"), Some(Locale::FRENCH));
+        assert_eq!(Locale::from_name("en_US"), Some(Locale::ENGLISH));
+        assert_eq!(Locale::from_name("xx"), None);
+    }
+}
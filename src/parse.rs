@@ -1,7 +1,9 @@
-use chrono::format::{ParseResult, Parsed};
+use std::borrow::Borrow;
+
+use chrono::format::{Item, ParseResult, Parsed, StrftimeItems};
 use chrono::offset::{LocalResult, Offset};
 use chrono::prelude::{Datelike, Timelike};
-use chrono::{DateTime, FixedOffset, TimeZone};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use core::str;
 
 // Wrapper functions to standardize the return type to i64
@@ -27,18 +29,125 @@ fn nanosecond<Tz: TimeZone>(dt: &DateTime<Tz>) -> i64 {
     dt.nanosecond() as i64
 }
 
-pub fn parse_partial<Tz: TimeZone>(
+/// Input once its missing fields have been completed, before any
+/// timezone has been chosen for it.
+#[derive(Debug, PartialEq)]
+pub enum Completed {
+    /// The input was an absolute timestamp (`@%s`), already in UTC.
+    Instant(DateTime<FixedOffset>),
+    /// A wall-clock time still to be placed in a timezone.
+    Local(NaiveDateTime),
+}
+
+pub fn complete_partial<Tz: TimeZone>(
     s: &str,
     fmt: &str,
     reference: &DateTime<Tz>,
     complete_with_zeroes: bool,
-) -> ParseResult<DateTime<FixedOffset>> {
-    use chrono::format::Numeric::{Day, Hour, Minute, Month, Nanosecond, Second, Year};
+) -> ParseResult<Completed> {
+    complete_items(s, StrftimeItems::new(fmt), reference, complete_with_zeroes)
+}
 
+/// Like `complete_partial`, with a format already tokenized by
+/// `compile`.
+pub fn complete_items<'a, Tz, I, B>(
+    s: &str,
+    items: I,
+    reference: &DateTime<Tz>,
+    complete_with_zeroes: bool,
+) -> ParseResult<Completed>
+where
+    Tz: TimeZone,
+    I: Iterator<Item = B>,
+    B: Borrow<Item<'a>>,
+{
     let mut parsed = Parsed::new();
     log::trace!("before: {:#?}", parsed);
-    chrono::format::parse(&mut parsed, s, chrono::format::StrftimeItems::new(fmt))?;
+    chrono::format::parse(&mut parsed, s, items)?;
     log::trace!("after: {:#?}", parsed);
+    complete_parsed(parsed, reference, complete_with_zeroes)
+}
+
+/// Tokenize `fmt` once, for formats tried on every input. Literals are
+/// copied, so the items outlive `fmt`.
+pub(crate) fn compile(fmt: &str) -> Vec<Item<'static>> {
+    StrftimeItems::new(fmt)
+        .map(|item| match item {
+            Item::Literal(s) => Item::OwnedLiteral(s.into()),
+            Item::Space(s) => Item::OwnedSpace(s.into()),
+            Item::OwnedLiteral(s) => Item::OwnedLiteral(s),
+            Item::OwnedSpace(s) => Item::OwnedSpace(s),
+            Item::Numeric(numeric, pad) => Item::Numeric(numeric, pad),
+            Item::Fixed(fixed) => Item::Fixed(fixed),
+            Item::Error => Item::Error,
+        })
+        .collect()
+}
+
+/// Fast path for the most common inputs, `%Y-%m-%d %H:%M:%S` and
+/// `%Y-%m-%d` with exactly their usual number of digits, giving the
+/// same result as `complete_partial` with these formats.
+///
+/// Returns `None` on any deviation, including invalid dates and leap
+/// seconds, so that the caller falls back to the formats.
+pub fn complete_iso<Tz: TimeZone>(
+    s: &str,
+    reference: &DateTime<Tz>,
+    complete_with_zeroes: bool,
+) -> Option<Completed> {
+    let b = s.as_bytes();
+    let num = |range: std::ops::Range<usize>| {
+        b[range].iter().try_fold(0u32, |n, d| {
+            d.is_ascii_digit().then(|| n * 10 + u32::from(d - b'0'))
+        })
+    };
+    if !matches!(b.len(), 10 | 19) || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    let date = NaiveDate::from_ymd_opt(num(0..4)? as i32, num(5..7)?, num(8..10)?)?;
+
+    let local = reference.naive_local();
+    let time = if b.len() == 19 {
+        if b[10] != b' ' || b[13] != b':' || b[16] != b':' {
+            return None;
+        }
+        let time = NaiveTime::from_hms_opt(num(11..13)?, num(14..16)?, num(17..19)?)?;
+        if complete_with_zeroes {
+            time
+        } else {
+            time.with_nanosecond(local.nanosecond())?
+        }
+    } else if complete_with_zeroes {
+        NaiveTime::MIN
+    } else {
+        local.time()
+    };
+    Some(Completed::Local(date.and_time(time)))
+}
+
+/// Like `complete_partial`, but only the beginning of `s` has to match
+/// `fmt`; the unparsed remainder is returned along with the result.
+pub fn parse_partial_prefix<'a, Tz: TimeZone>(
+    s: &'a str,
+    fmt: &str,
+    reference: &DateTime<Tz>,
+    complete_with_zeroes: bool,
+) -> ParseResult<(Completed, &'a str)> {
+    let mut parsed = Parsed::new();
+    let rest = chrono::format::parse_and_remainder(&mut parsed, s, StrftimeItems::new(fmt))?;
+    log::trace!("after: {:#?}, remainder {:?}", parsed, rest);
+    Ok((
+        complete_parsed(parsed, reference, complete_with_zeroes)?,
+        rest,
+    ))
+}
+
+fn complete_parsed<Tz: TimeZone>(
+    mut parsed: Parsed,
+    reference: &DateTime<Tz>,
+    complete_with_zeroes: bool,
+) -> ParseResult<Completed> {
+    use chrono::format::Numeric::{Day, Hour, Minute, Month, Nanosecond, Second, Year};
 
     type Getter<T, Tz> = fn(&DateTime<Tz>) -> T;
     type Setter = fn(&mut Parsed, i64) -> ParseResult<()>;
@@ -48,9 +157,26 @@ pub fn parse_partial<Tz: TimeZone>(
         let mut complete_with_zeroes = complete_with_zeroes;
         for num in nums.iter() {
             let (get, set, replace, min): (Getter<i64, Tz>, Setter, bool, i64) = match num {
-                Year => (year, Parsed::set_year, parsed.year.is_none(), 1970),
-                Month => (month, Parsed::set_month, parsed.month.is_none(), 1),
-                Day => (day, Parsed::set_day, parsed.day.is_none(), 1),
+                // `%y` gives a year too, its century is left to the caller
+                Year => (
+                    year,
+                    Parsed::set_year,
+                    parsed.year.is_none() && parsed.year_mod_100.is_none(),
+                    1970,
+                ),
+                // a day of year (%j) stands for both the month and the day
+                Month => (
+                    month,
+                    Parsed::set_month,
+                    parsed.month.is_none() && parsed.ordinal.is_none(),
+                    1,
+                ),
+                Day => (
+                    day,
+                    Parsed::set_day,
+                    parsed.day.is_none() && parsed.ordinal.is_none(),
+                    1,
+                ),
                 Hour => (
                     hour,
                     Parsed::set_hour,
@@ -78,35 +204,282 @@ pub fn parse_partial<Tz: TimeZone>(
             }
         }
     }
-    // Resolve the final offset using the reference timezone at the target local datetime
     // Build naive local datetime without applying the reference's current offset
     let naive = parsed.to_naive_datetime_with_offset(0)?;
 
     // If input provided an absolute timestamp (@%s), treat it as UTC
     if parsed.timestamp.is_some() {
         let off0 = FixedOffset::east_opt(0).unwrap();
-        return Ok(off0.from_utc_datetime(&naive));
+        return Ok(Completed::Instant(off0.from_utc_datetime(&naive)));
     }
+    // An explicit offset in the input (`%+`) fixes the timezone
+    if parsed.offset.is_some() {
+        return Ok(Completed::Instant(parsed.to_datetime()?));
+    }
+    Ok(Completed::Local(naive))
+}
 
-    // Map the naive local time into the system local timezone to pick the correct DST offset
-    // Choose resolution mode based on reference: UTC-like keeps UTC, otherwise use system local (with DST)
-    let dt_fixed: DateTime<FixedOffset> = if reference.offset().fix().local_minus_utc() == 0 {
-        let off0 = FixedOffset::east_opt(0).unwrap();
-        off0.from_utc_datetime(&naive)
-    } else {
-        match chrono::Local.from_local_datetime(&naive) {
-            LocalResult::Single(dt) => dt.with_timezone(&dt.offset().fix()),
-            LocalResult::Ambiguous(a, _b) => a.with_timezone(&a.offset().fix()), // pick earlier
-            LocalResult::None => unreachable!(),
+/// How an input was turned into an absolute time, to diagnose
+/// unexpected offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseDetails<'a> {
+    pub datetime: DateTime<FixedOffset>,
+    /// Wall-clock time the input resolved to before a timezone was
+    /// chosen (the UTC time for `@` timestamps).
+    pub wall_clock: NaiveDateTime,
+    /// Offset chosen for `wall_clock`.
+    pub offset: FixedOffset,
+    /// When the wall-clock time happens twice (DST fold), the other
+    /// candidate, which was not picked.
+    pub alternative: Option<DateTime<FixedOffset>>,
+    /// Format that read the input, `None` for keywords and other
+    /// expressions. Borrowed from the parser.
+    pub format: Option<&'a str>,
+}
+
+impl ParseDetails<'_> {
+    /// Details of a time that needed no timezone resolution.
+    pub fn exact(datetime: DateTime<FixedOffset>) -> Self {
+        ParseDetails {
+            datetime,
+            wall_clock: datetime.naive_local(),
+            offset: *datetime.offset(),
+            alternative: None,
+            format: None,
+        }
+    }
+
+    /// Whether the wall-clock time was ambiguous and one of two
+    /// candidates had to be picked.
+    pub fn disambiguated(&self) -> bool {
+        self.alternative.is_some()
+    }
+}
+
+/// Timezone wall-clock times are placed in, see `ResolutionZone::of`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResolutionZone {
+    /// The reference's own timezone, with its DST rules.
+    Reference,
+    Utc,
+    /// The system timezone, picking the DST offset at the target time.
+    System,
+}
+
+impl ResolutionZone {
+    /// With `keep_zone`, the reference's own timezone. Otherwise UTC if
+    /// the reference's timezone is UTC all year round (`Utc`, or a zero
+    /// `FixedOffset`), and the system timezone for any other: the
+    /// offset the reference happens to have at its own date, such as
+    /// +00:00 for London in winter, doesn't decide.
+    pub(crate) fn of<Tz: TimeZone>(reference: &DateTime<Tz>, keep_zone: bool) -> Self {
+        if keep_zone {
+            ResolutionZone::Reference
+        } else if always_utc(&reference.timezone(), reference.year()) {
+            ResolutionZone::Utc
+        } else {
+            ResolutionZone::System
         }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn name(self) -> &'static str {
+        match self {
+            ResolutionZone::Reference => "reference",
+            ResolutionZone::Utc => "utc",
+            ResolutionZone::System => "system",
+        }
+    }
+}
+
+// Whether `zone` is at UTC+0 in both winter and summer of `year`, and
+// so has no DST in either hemisphere
+fn always_utc<Tz: TimeZone>(zone: &Tz, year: i32) -> bool {
+    [1, 7].into_iter().all(|month| {
+        NaiveDate::from_ymd_opt(year, month, 1).is_some_and(|date| {
+            let instant = date.and_time(NaiveTime::MIN);
+            zone.offset_from_utc_datetime(&instant)
+                .fix()
+                .local_minus_utc()
+                == 0
+        })
+    })
+}
+
+/// Place a wall-clock time in the timezone implied by `reference`, see
+/// `ResolutionZone::of`. Returns `None` when the time doesn't exist
+/// there, skipped by a DST change.
+pub fn resolve_details<Tz: TimeZone>(
+    naive: &NaiveDateTime,
+    reference: &DateTime<Tz>,
+    keep_zone: bool,
+) -> Option<ParseDetails<'static>> {
+    let zone = ResolutionZone::of(reference, keep_zone);
+    let (dt_fixed, alternative) = match zone {
+        ResolutionZone::Reference => {
+            fix_local_result(reference.timezone().from_local_datetime(naive))?
+        }
+        ResolutionZone::Utc => (naive.and_utc().fixed_offset(), None),
+        ResolutionZone::System => fix_local_result(chrono::Local.from_local_datetime(naive))?,
     };
-    Ok(dt_fixed)
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        wall_clock = %naive,
+        offset = %dt_fixed.offset(),
+        zone = zone.name(),
+        ambiguous = alternative.is_some(),
+        "resolved wall-clock time"
+    );
+    Some(ParseDetails {
+        datetime: dt_fixed,
+        wall_clock: *naive,
+        offset: *dt_fixed.offset(),
+        alternative,
+        format: None,
+    })
+}
+
+/// Wall-clock time of `dt` in the timezone `resolve_details` places
+/// times in, so that shifting it and resolving it again stays in that
+/// zone.
+pub(crate) fn wall_clock_for<Tz: TimeZone>(
+    dt: &DateTime<FixedOffset>,
+    reference: &DateTime<Tz>,
+    keep_zone: bool,
+) -> NaiveDateTime {
+    match ResolutionZone::of(reference, keep_zone) {
+        ResolutionZone::Reference => dt.with_timezone(&reference.timezone()).naive_local(),
+        ResolutionZone::Utc => dt.naive_utc(),
+        ResolutionZone::System => dt.with_timezone(&chrono::Local).naive_local(),
+    }
+}
+
+fn fix_local_result<Tz: TimeZone>(
+    result: LocalResult<DateTime<Tz>>,
+) -> Option<(DateTime<FixedOffset>, Option<DateTime<FixedOffset>>)> {
+    match result {
+        LocalResult::Single(dt) => Some((dt.fixed_offset(), None)),
+        // pick the smaller offset, which is the later instant, whatever
+        // order `Tz` gives them in
+        LocalResult::Ambiguous(a, b) => {
+            let (a, b) = (a.fixed_offset(), b.fixed_offset());
+            Some((a.max(b), Some(a.min(b))))
+        }
+        LocalResult::None => None,
+    }
+}
+
+/// Whether `s` has a numeric UTC offset (`+02:00`, `-0500`) right
+/// after a date or time, but followed by other words. Offsets are only
+/// accepted at the end, so such inputs are rejected rather than matched
+/// by a wrong format. A leading `+12` is a relative offset, not a UTC
+/// offset.
+pub fn has_misplaced_offset(s: &str) -> bool {
+    let words: Vec<&str> = s.split_whitespace().collect();
+    words
+        .windows(3)
+        .any(|window| is_date_or_time(window[0]) && is_offset(window[1]))
+}
+
+fn is_date_or_time(word: &str) -> bool {
+    word.contains(|c: char| c.is_ascii_digit())
+        && word.contains(['-', ':', '/', '.'])
+        && word
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | ':' | '/' | '.' | 'T'))
+}
+
+fn is_offset(word: &str) -> bool {
+    let Some(digits) = word.strip_prefix(['+', '-']) else {
+        return false;
+    };
+    let digits = match digits.split_once(':') {
+        Some((hours, minutes)) if hours.len() == 2 && minutes.len() == 2 => {
+            format!("{}{}", hours, minutes)
+        }
+        Some(_) => return false,
+        None => digits.to_string(),
+    };
+    matches!(digits.len(), 2 | 4) && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Split an input ending with a `UTC±N` or `GMT±N` zone (`10:15
+/// UTC+2`, `GMT-05:30`, `UTC`) into the rest and the zone's offset.
+///
+/// The sign is the intuitive one: `UTC+2` is `+02:00`, not the POSIX
+/// `TZ` reading where it would be two hours west.
+pub fn split_utc_offset(s: &str) -> Option<(&str, FixedOffset)> {
+    let s = s.trim_end();
+    let start = s.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let (rest, word) = s.split_at(start);
+    let offset = word
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("utc") || prefix.eq_ignore_ascii_case("gmt"))
+        .and_then(|_| zone_offset(&word[3..]))?;
+    Some((rest.trim_end(), offset))
+}
+
+// `+2`, `-05`, `+5:30`, `+0530` or nothing for UTC itself
+fn zone_offset(s: &str) -> Option<FixedOffset> {
+    if s.is_empty() {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = match s.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = &s[1..];
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+        Some(_) => return None,
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "00"),
+    };
+    if !(1..=2).contains(&hours.len())
+        || !format!("{}{}", hours, minutes)
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Era markers, and whether they count years before the common era.
+pub const ERAS: [(&str, bool); 4] = [("BC", true), ("BCE", true), ("AD", false), ("CE", false)];
+
+/// Rewrite an input ending with an era marker (`44 BC`, `2025 AD`) so
+/// that its leading year uses astronomical numbering, as chrono does:
+/// 1 AD is year 1, 1 BC is year 0, 2 BC is year -1, and so on.
+///
+/// Returns `None` when there is no era marker, or when the input doesn't
+/// start with a valid year (there is no year 0 in either era).
+pub fn strip_era(s: &str) -> Option<String> {
+    let (rest, era) = s.trim_end().rsplit_once(char::is_whitespace)?;
+    let era = era.to_uppercase();
+    let (_, bc) = ERAS.iter().find(|(name, _)| *name == era)?;
+    let rest = rest.trim();
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    if !matches!(rest[digits..].chars().next(), None | Some('-' | ' ')) {
+        return None;
+    }
+    let year: i64 = rest[..digits].parse().ok().filter(|y| *y > 0)?;
+    let year = if *bc { 1 - year } else { year };
+    log::trace!("Era {:?} maps year to {}", era, year);
+    Some(format!("{}{}", year, &rest[digits..]))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Local, Utc};
+    use chrono::{Local, NaiveDate, Utc};
 
     fn pp<Tz: TimeZone>(
         s: &str,
@@ -114,7 +487,23 @@ mod tests {
         dt: &DateTime<Tz>,
         complete_with_zeroes: bool,
     ) -> String {
-        format!("{:?}", parse_partial(s, fmt, dt, complete_with_zeroes))
+        let result =
+            complete_partial(s, fmt, dt, complete_with_zeroes).map(|completed| match completed {
+                Completed::Instant(dt) => dt,
+                Completed::Local(naive) => resolve_details(&naive, dt, false).unwrap().datetime,
+            });
+        format!("{:?}", result)
+    }
+
+    fn local_details<Tz: TimeZone>(
+        s: &str,
+        fmt: &str,
+        dt: &DateTime<Tz>,
+    ) -> Option<ParseDetails<'static>> {
+        match complete_partial(s, fmt, dt, true).unwrap() {
+            Completed::Local(naive) => resolve_details(&naive, dt, false),
+            Completed::Instant(_) => unreachable!(),
+        }
     }
 
     #[test]
@@ -160,6 +549,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_strip_era() {
+        assert_eq!(strip_era("1 BC"), Some("0".to_string()));
+        assert_eq!(strip_era("44 bc"), Some("-43".to_string()));
+        assert_eq!(strip_era("44-03-15 BC"), Some("-43-03-15".to_string()));
+        assert_eq!(strip_era("2025 AD"), Some("2025".to_string()));
+        assert_eq!(strip_era("0 BC"), None);
+        assert_eq!(strip_era("2025"), None);
+        assert_eq!(strip_era("10:15 BC"), None);
+    }
+
+    #[test]
+    fn test_has_misplaced_offset() {
+        assert!(has_misplaced_offset("2025-10-27 +02:00 10:15"));
+        assert!(has_misplaced_offset("10:15 +0200 2025-10-27"));
+        // a leading offset is relative
+        assert!(!has_misplaced_offset("+0200 10:15"));
+        assert!(!has_misplaced_offset("+12 2025-10-27"));
+        assert!(!has_misplaced_offset("2025-10-27 10:15 +02:00"));
+        assert!(!has_misplaced_offset("2025-10-27T10:15:00+02:00"));
+        assert!(!has_misplaced_offset("tomorrow + 3h"));
+        assert!(!has_misplaced_offset("+1d 12h"));
+    }
+
+    #[test]
+    fn test_complete_iso_matches_formats() {
+        let dt = Utc
+            .with_ymd_and_hms(2014, 7, 8, 9, 10, 11)
+            .unwrap()
+            .with_nanosecond(123_456_789)
+            .unwrap();
+        let fmt = |s: &str| {
+            if s.len() > 10 {
+                "%Y-%m-%d %H:%M:%S"
+            } else {
+                "%Y-%m-%d"
+            }
+        };
+        let fast = [
+            "2025-10-27",
+            "2025-10-27 10:15:00",
+            "0000-01-01 00:00:00",
+            "9999-12-31 23:59:59",
+            "2024-02-29",
+        ];
+        // invalid, or left to the formats
+        let slow = [
+            "2025-02-30",
+            "2025-13-01 10:15:00",
+            "2025-10-27 24:00:00",
+            "2025-10-27 10:60:00",
+            "2016-12-31 23:59:60",
+            "2025-1-27 10:15:00",
+            "+025-10-27",
+            " 2025-10-27",
+            "2025-10-27T10:15:00",
+            "2025-10-27 10:15",
+            "2025-10-2x",
+        ];
+        for complete_with_zeroes in [true, false] {
+            for input in fast {
+                let expected = complete_partial(input, fmt(input), &dt, complete_with_zeroes);
+                assert_eq!(
+                    complete_iso(input, &dt, complete_with_zeroes),
+                    Some(expected.unwrap()),
+                    "{input:?}"
+                );
+            }
+            for input in slow {
+                assert_eq!(
+                    complete_iso(input, &dt, complete_with_zeroes),
+                    None,
+                    "{input:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_utc_offset() {
+        let east = |secs| FixedOffset::east_opt(secs).unwrap();
+        assert_eq!(split_utc_offset("10:15 UTC+2"), Some(("10:15", east(7200))));
+        assert_eq!(split_utc_offset("GMT-5"), Some(("", east(-5 * 3600))));
+        assert_eq!(
+            split_utc_offset("10:15 utc+05:30"),
+            Some(("10:15", east(19800)))
+        );
+        assert_eq!(
+            split_utc_offset("10:15 GMT-0930"),
+            Some(("10:15", east(-34200)))
+        );
+        assert_eq!(split_utc_offset("10:15 UTC"), Some(("10:15", east(0))));
+        assert_eq!(split_utc_offset("10:15 UTC+"), None);
+        assert_eq!(split_utc_offset("10:15 UTC+123"), None);
+        assert_eq!(split_utc_offset("10:15 UTC+2:60"), None);
+        assert_eq!(split_utc_offset("10:15 UTC+99"), None);
+        assert_eq!(split_utc_offset("10:15 +02:00"), None);
+    }
+
+    #[test]
+    fn test_parse_partial_prefix() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        match parse_partial_prefix("2015 toto", "%Y", &dt, true) {
+            Ok((Completed::Local(naive), rest)) => {
+                assert_eq!(naive.to_string(), "2015-01-01 00:00:00");
+                assert_eq!(rest, " toto");
+            }
+            _ => panic!("expected a wall-clock time with a remainder"),
+        }
+        assert!(parse_partial_prefix("toto", "%Y", &dt, true).is_err());
+    }
+
+    #[test]
+    fn test_resolve_details_keeps_reference_zone() {
+        let naive = NaiveDate::from_ymd_opt(2025, 7, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let winter = FixedOffset::east_opt(-5 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 1, 1, 12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            resolve_details(&naive, &winter, true).map(|d| d.datetime.to_string()),
+            Some("2025-07-01 10:00:00 -05:00".to_string())
+        );
+        let utc = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            resolve_details(&naive, &utc, true).map(|d| d.datetime.to_string()),
+            Some("2025-07-01 10:00:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_details_reports_fold() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let reference = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 12, 1, 12, 0, 0)
+            .unwrap();
+
+        let details =
+            local_details("2025-10-26 02:30", "%Y-%m-%d %H:%M", &reference).expect("resolve");
+        assert!(details.disambiguated());
+        assert_eq!(details.wall_clock.to_string(), "2025-10-26 02:30:00");
+        assert_eq!(details.datetime.to_string(), "2025-10-26 02:30:00 +01:00");
+        assert_eq!(details.offset, FixedOffset::east_opt(3600).unwrap());
+        assert_eq!(
+            details.alternative.map(|dt| dt.to_string()),
+            Some("2025-10-26 02:30:00 +02:00".to_string())
+        );
+
+        let details =
+            local_details("2025-10-27 02:30", "%Y-%m-%d %H:%M", &reference).expect("resolve");
+        assert!(!details.disambiguated());
+
+        // skipped when moving to summer time
+        assert_eq!(
+            local_details("2025-03-30 02:30", "%Y-%m-%d %H:%M", &reference),
+            None
+        );
+    }
+
     #[test]
     fn test_fill_right() {
         // Use Utc to have a predictable timezone offset (+00:00)
@@ -0,0 +1,3890 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use chrono::format::{Item, ParseResult};
+use chrono::{DateTime, Datelike, Days, FixedOffset, NaiveDate, TimeZone, Timelike, Utc, Weekday};
+
+use crate::locale::Locale;
+use crate::parse::{Completed, ParseDetails};
+use crate::relative::{self, Anchor, Unit, WeekStart};
+use crate::{
+    OpenTimespan, ParseError, TIMEPARSER_FORMATS, TIMEPARSER_ITEMS, TIMESPAN_SEPARATORS,
+    TimespanBounds, duration, parse,
+};
+
+/// How fields missing from the input are completed.
+///
+/// With a reference of `2014-07-08 09:10:11.5`:
+///
+/// | input                 | `Zeroes`              | `Reference`             |
+/// |-----------------------|-----------------------|-------------------------|
+/// | `2015`                | `2015-01-01 00:00:00` | `2015-07-08 09:10:11.5` |
+/// | `Jan 5`               | `2014-01-05 00:00:00` | `2014-01-05 09:10:11.5` |
+/// | `10:15`               | `2014-07-08 10:15:00` | `2014-07-08 10:15:11.5` |
+/// | `2015-02-01 23:22:12` | `2015-02-01 23:22:12` | `2015-02-01 23:22:12.5` |
+///
+/// Keywords and relative expressions (`tomorrow`, `+2h`, `friday`)
+/// follow their own rules whatever the mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fill {
+    /// Fields finer than the finest one given are set to their minimum
+    /// (midnight, the 1st, January) and the coarser missing ones come
+    /// from the reference: the input is the start of the period it
+    /// names.
+    #[default]
+    Zeroes,
+    /// Every missing field comes from the reference, down to the
+    /// fraction of a second.
+    Reference,
+}
+
+/// What to do with a timespan whose end comes before its start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReversedSpan {
+    #[default]
+    Reject,
+    /// Exchange the bounds: `14:00..10:00` is read as `10:00..14:00`.
+    Swap,
+}
+
+/// Which time to pick when a wall-clock time happens twice, as when
+/// DST ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbiguousPolicy {
+    Earlier,
+    /// The time with the smaller offset, usually standard time.
+    #[default]
+    Later,
+    /// Fail with `ParseError::AmbiguousLocal`.
+    Reject,
+}
+
+/// Year given to a date written without one, such as `12-25`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YearInference {
+    /// The reference's year.
+    #[default]
+    Current,
+    /// The year putting the date closest to the reference: `12-25` read
+    /// on January 2nd is the Christmas a week before.
+    Nearest,
+    /// The first year putting the date at or after the reference.
+    NextFuture,
+}
+
+/// What to do with a leap second such as `23:59:60`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeapSecond {
+    /// Follow chrono's convention: the result is second 59 with a
+    /// nanosecond field of one second or more, and compares between
+    /// `23:59:59` and the next midnight.
+    #[default]
+    Accept,
+    /// Fail with `ParseError::LeapSecond`.
+    Reject,
+}
+
+/// Structured description of the inputs a `Parser` accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportedSyntax {
+    /// strftime formats tried in order on the input.
+    pub formats: Vec<String>,
+    /// Words recognized by name, in their canonical spelling.
+    pub keywords: Vec<String>,
+    /// Grammar of the expressions built from keywords, `<a|b>`
+    /// listing alternatives and `[...]` optional parts.
+    pub expressions: Vec<String>,
+    /// Strings splitting a timespan into its start and end.
+    pub timespan_separators: Vec<String>,
+}
+
+/// Result of `try_parse`, telling apart the ways an input can fail to
+/// give a single time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseOutcome {
+    Parsed(DateTime<FixedOffset>),
+    /// The wall-clock time happens twice (DST fold); both candidates in
+    /// chronological order.
+    Ambiguous(Vec<DateTime<FixedOffset>>),
+    /// The wall-clock time is skipped by a DST change.
+    NonexistentLocal,
+    NoMatch,
+    /// The input was understood but lands outside the representable
+    /// range.
+    OutOfRange,
+}
+
+/// Reusable parsing configuration.
+///
+/// The free functions of this crate use `Parser::new()`; build a
+/// `Parser` yourself to change its options.
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    fill: Fill,
+    verbose_errors: bool,
+    week_start: WeekStart,
+    locale: Locale,
+    timestamp_epoch: DateTime<Utc>,
+    floor_at_reference: bool,
+    keep_reference_zone: bool,
+    prefer_utc: bool,
+    bare_number_as_hour: bool,
+    two_digit_year_pivot: Option<i32>,
+    anchor_date: Option<NaiveDate>,
+    require_explicit_range: bool,
+    allow_fully_open: bool,
+    whole_seconds: bool,
+    keywords: Vec<Keyword>,
+    allow_fractional_clock: bool,
+    fractional_minutes: bool,
+    timespan_separator: Option<String>,
+    default_span: Option<chrono::Duration>,
+    span_matches_precision: bool,
+    reversed_span: ReversedSpan,
+    dash_as_day_range: bool,
+    roll_over_seconds_of_day: bool,
+    leap_seconds: LeapSecond,
+    year_inference: YearInference,
+    ambiguous: AmbiguousPolicy,
+    postprocessors: Vec<Postprocessor>,
+    matchers: Vec<Matcher>,
+    formats: Option<Vec<Format>>,
+    extra_formats: Vec<Format>,
+}
+
+/// Format set with `Parser::with_formats` or `Parser::add_format`,
+/// along with its items, tokenized once.
+#[derive(Debug, Clone)]
+struct Format {
+    format: String,
+    items: Vec<Item<'static>>,
+}
+
+impl Format {
+    fn new(format: &str) -> Self {
+        Format {
+            format: format.to_string(),
+            items: parse::compile(format),
+        }
+    }
+}
+
+/// A grammar of its own, plugged into a `Parser` with `with_matcher`.
+///
+/// `resolve` returns the time `input` stands for, or `None` to let the
+/// next matcher, and finally the built-in grammar, try. `Parser` is
+/// itself a `TimeToken`, so a differently configured parser can be
+/// chained as a fallback.
+pub trait TimeToken: Send + Sync {
+    fn resolve(
+        &self,
+        input: &str,
+        reference: &DateTime<FixedOffset>,
+    ) -> Option<DateTime<FixedOffset>>;
+}
+
+impl TimeToken for Parser {
+    fn resolve(
+        &self,
+        input: &str,
+        reference: &DateTime<FixedOffset>,
+    ) -> Option<DateTime<FixedOffset>> {
+        self.parse(input, reference).ok()
+    }
+}
+
+/// Grammar registered with `Parser::with_matcher`.
+#[derive(Clone)]
+struct Matcher(Arc<dyn TimeToken>);
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matcher").finish_non_exhaustive()
+    }
+}
+
+type ResolveKeyword = dyn Fn(&DateTime<FixedOffset>) -> DateTime<FixedOffset> + Send + Sync;
+
+/// Word registered with `Parser::add_keyword`.
+#[derive(Clone)]
+struct Keyword {
+    name: String,
+    resolve: Arc<ResolveKeyword>,
+}
+
+impl fmt::Debug for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyword")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+type Postprocess = dyn Fn(DateTime<FixedOffset>) -> DateTime<FixedOffset> + Send + Sync;
+
+/// Function registered with `Parser::with_postprocess`.
+#[derive(Clone)]
+struct Postprocessor(Arc<Postprocess>);
+
+impl fmt::Debug for Postprocessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Postprocessor").finish_non_exhaustive()
+    }
+}
+
+/// Separate the center of a timespan from its half-length, as in
+/// `12:00 ±2h`; `~` reads "approximately" and is easier to type
+/// (`12:00~2h`).
+const WINDOW_MARKERS: [char; 2] = ['±', '~'];
+
+/// First year of the window two-digit years fall in, as in POSIX
+/// (`69` is 1969, `68` is 2068).
+const DEFAULT_YEAR_PIVOT: i32 = 1969;
+
+/// Smallest number the built-in `%Y` format reads as a year on its
+/// own. Below, `930` or `1015` look like clock times, not years.
+const MIN_BARE_YEAR: u32 = 1900;
+
+impl Parser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How fields missing from the input are completed.
+    pub fn fill(mut self, fill: Fill) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// List the formats that were tried in the error message when an
+    /// input can't be parsed.
+    pub fn verbose_errors(mut self, verbose: bool) -> Self {
+        self.verbose_errors = verbose;
+        self
+    }
+
+    /// First day of the week for week-based expressions such as
+    /// `start of next week`.
+    pub fn week_start(mut self, week_start: WeekStart) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Language of month/weekday names, decimal separator and order of
+    /// slash-separated dates. Defaults to `Locale::ENGLISH`.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Origin of `@N` timestamps, which are read as N seconds from it.
+    /// Defaults to the Unix epoch; use e.g. 1980-01-06 for GPS time.
+    /// `@@N` day counts are not affected and always start from
+    /// 1970-01-01.
+    pub fn timestamp_epoch(mut self, epoch: DateTime<Utc>) -> Self {
+        self.timestamp_epoch = epoch;
+        self
+    }
+
+    /// Clamp results earlier than the reference up to the reference,
+    /// e.g. for "since" queries that can't look back further.
+    pub fn floor_at_reference(mut self, floor: bool) -> Self {
+        self.floor_at_reference = floor;
+        self
+    }
+
+    /// Read a bare one or two-digit number such as `09` as an hour of
+    /// the reference day instead of a minute of the reference hour.
+    pub fn bare_number_as_hour(mut self, as_hour: bool) -> Self {
+        self.bare_number_as_hour = as_hour;
+        self
+    }
+
+    /// First year of the hundred-year window two-digit years (`%y`)
+    /// are placed in. Defaults to 1969, so `50` is 2050; use e.g. 1900
+    /// to read all of them as 19xx.
+    pub fn two_digit_year_pivot(mut self, pivot: i32) -> Self {
+        self.two_digit_year_pivot = Some(pivot);
+        self
+    }
+
+    /// Date the start of a timespan takes when it gives none, as in
+    /// `10:00..14:00`. Defaults to the reference's date; the end then
+    /// inherits the date of the start as usual.
+    pub fn anchor_date(mut self, date: NaiveDate) -> Self {
+        self.anchor_date = Some(date);
+        self
+    }
+
+    /// Reject timespans without separator instead of reading them as
+    /// the day following the time given.
+    pub fn require_explicit_range(mut self, require: bool) -> Self {
+        self.require_explicit_range = require;
+        self
+    }
+
+    /// Accept `..` in `parse_timespan_open` as unbounded on both sides,
+    /// instead of rejecting it as most likely a mistake.
+    pub fn allow_fully_open(mut self, allow: bool) -> Self {
+        self.allow_fully_open = allow;
+        self
+    }
+
+    /// String splitting a timespan into its start and end, instead of
+    /// the default `..`, ` to ` and `->`, e.g. ` - `.
+    pub fn timespan_separator(mut self, separator: &str) -> Self {
+        self.timespan_separator = Some(separator.to_string());
+        self
+    }
+
+    /// Length of a timespan given as a single time. Defaults to one
+    /// day.
+    pub fn default_span(mut self, span: chrono::Duration) -> Self {
+        self.default_span = Some(span);
+        self
+    }
+
+    /// Give a timespan made of a single time the length of the input's
+    /// least significant field: `10:15` spans a minute, `2025-10`
+    /// the month of October. Inputs not read by a format, such as
+    /// keywords and offsets, keep `default_span`.
+    pub fn span_matches_precision(mut self, matches: bool) -> Self {
+        self.span_matches_precision = matches;
+        self
+    }
+
+    /// What to do with a timespan ending before it starts. Defaults to
+    /// rejecting it.
+    pub fn reversed_span(mut self, reversed: ReversedSpan) -> Self {
+        self.reversed_span = reversed;
+        self
+    }
+
+    /// Read a timespan such as `1-12` as the 1st through the 12th of
+    /// the reference month, instead of the date January 12th.
+    pub fn dash_as_day_range(mut self, day_range: bool) -> Self {
+        self.dash_as_day_range = day_range;
+        self
+    }
+
+    /// Let `s<seconds>` times of day go past midnight into the next
+    /// days (`s90000` is 01:00 the day after) instead of rejecting
+    /// them.
+    pub fn roll_over_seconds_of_day(mut self, roll_over: bool) -> Self {
+        self.roll_over_seconds_of_day = roll_over;
+        self
+    }
+
+    /// Choose between the two times a wall-clock time stands for in a
+    /// DST fold. Defaults to the later one; either way, the other is
+    /// reported as `ParseDetails::alternative`.
+    pub fn ambiguous(mut self, policy: AmbiguousPolicy) -> Self {
+        self.ambiguous = policy;
+        self
+    }
+
+    /// Choose the year of dates written with a month but no year.
+    pub fn year_inference(mut self, inference: YearInference) -> Self {
+        self.year_inference = inference;
+        self
+    }
+
+    /// Accept or reject inputs whose seconds field is `60`.
+    pub fn leap_seconds(mut self, leap_seconds: LeapSecond) -> Self {
+        self.leap_seconds = leap_seconds;
+        self
+    }
+
+    /// Read a decimal number of hours such as `10.5` as a time of the
+    /// reference day (10:30), as timesheets do.
+    pub fn allow_fractional_clock(mut self, allow: bool) -> Self {
+        self.allow_fractional_clock = allow;
+        self
+    }
+
+    /// Read a decimal part after the minutes of a clock time without
+    /// seconds as a fraction of a minute: `10:15.5` is 10:15:30.
+    pub fn fractional_minutes(mut self, fractional: bool) -> Self {
+        self.fractional_minutes = fractional;
+        self
+    }
+
+    /// Drop the fractional seconds of every result, e.g. for storage
+    /// with a one-second resolution.
+    pub fn whole_seconds(mut self, whole: bool) -> Self {
+        self.whole_seconds = whole;
+        self
+    }
+
+    /// Read `name` (in any case) as the time `resolve` returns for the
+    /// reference, e.g. `shift_start`. Like `now`, it can be followed by
+    /// an offset (`shift_start + 2h`).
+    ///
+    /// Custom keywords are tried before any built-in syntax, so they
+    /// take precedence over `now`, `today`, ...; registering a name
+    /// again replaces its previous meaning. A name containing
+    /// whitespace, `+` or `-` never matches.
+    pub fn add_keyword(
+        mut self,
+        name: &str,
+        resolve: impl Fn(&DateTime<FixedOffset>) -> DateTime<FixedOffset> + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.to_lowercase();
+        self.keywords.retain(|k| k.name != name);
+        self.keywords.push(Keyword {
+            name,
+            resolve: Arc::new(resolve),
+        });
+        self
+    }
+
+    /// Pass every result through `f`, e.g. to truncate, clamp or
+    /// convert it, after the other options have been applied. Functions
+    /// registered by successive calls run in order.
+    pub fn with_postprocess(
+        mut self,
+        f: impl Fn(DateTime<FixedOffset>) -> DateTime<FixedOffset> + Send + Sync + 'static,
+    ) -> Self {
+        self.postprocessors.push(Postprocessor(Arc::new(f)));
+        self
+    }
+
+    /// Try `matcher` on every input before the built-in grammar.
+    /// Matchers registered by successive calls are tried in order, and
+    /// the first result is kept as is, with only the postprocessing
+    /// options applied.
+    pub fn with_matcher(mut self, matcher: impl TimeToken + 'static) -> Self {
+        self.matchers.push(Matcher(Arc::new(matcher)));
+        self
+    }
+
+    /// Use `formats`, in this order, instead of `TIMEPARSER_FORMATS`
+    /// and those of the locale's date order, e.g. to only accept ISO
+    /// dates. Keywords and relative expressions are still read.
+    pub fn with_formats<S: AsRef<str>>(mut self, formats: &[S]) -> Self {
+        self.formats = Some(formats.iter().map(|f| Format::new(f.as_ref())).collect());
+        self
+    }
+
+    /// Try `format` after the other formats.
+    pub fn add_format(mut self, format: &str) -> Self {
+        self.extra_formats.push(Format::new(format));
+        self
+    }
+
+    /// Place wall-clock times in the reference's own timezone, following
+    /// its DST rules, rather than the system one.
+    pub fn keep_reference_zone(mut self, keep: bool) -> Self {
+        self.keep_reference_zone = keep;
+        self
+    }
+
+    /// Place wall-clock times in UTC, and take today's date from UTC,
+    /// whatever the reference's offset, instead of going through the
+    /// system timezone.
+    pub fn prefer_utc(mut self, prefer: bool) -> Self {
+        self.prefer_utc = prefer;
+        self
+    }
+
+    /// Formats tried in order on the input.
+    pub(crate) fn formats(&self) -> impl Iterator<Item = &str> + '_ {
+        self.compiled_formats().map(|(format, _)| format)
+    }
+
+    // `formats` along with their items, tokenized once for all
+    fn compiled_formats(&self) -> impl Iterator<Item = (&str, &[Item<'static>])> + '_ {
+        let order = self.locale.date_order;
+        let builtin = self.formats.is_none().then(|| {
+            TIMEPARSER_FORMATS
+                .iter()
+                .chain(order.formats())
+                .copied()
+                .zip(TIMEPARSER_ITEMS.iter().chain(order.items()))
+        });
+        let custom = self.formats.iter().flatten().chain(&self.extra_formats);
+        builtin
+            .into_iter()
+            .flatten()
+            .map(|(format, items)| (format, items.as_slice()))
+            .chain(custom.map(|f| (f.format.as_str(), f.items.as_slice())))
+    }
+
+    pub fn parse<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        self.parse_detailed(timestr, reference)
+            .map(|details| details.datetime)
+    }
+
+    /// Like `parse`, but never fails: every outcome, including DST
+    /// folds, is reported as a `ParseOutcome`.
+    pub fn try_parse<Tz: TimeZone>(&self, timestr: &str, reference: &DateTime<Tz>) -> ParseOutcome {
+        match self.parse_detailed(timestr, reference) {
+            Ok(ParseDetails {
+                datetime,
+                alternative: Some(alternative),
+                ..
+            }) => {
+                let mut candidates = vec![datetime, alternative];
+                candidates.sort();
+                ParseOutcome::Ambiguous(candidates)
+            }
+            Ok(details) => ParseOutcome::Parsed(details.datetime),
+            Err(ParseError::AmbiguousLocal { earlier, later, .. }) => {
+                ParseOutcome::Ambiguous(vec![earlier, later])
+            }
+            Err(ParseError::NonexistentLocal { .. }) => ParseOutcome::NonexistentLocal,
+            Err(ParseError::OutOfRange { .. }) => ParseOutcome::OutOfRange,
+            Err(_) => ParseOutcome::NoMatch,
+        }
+    }
+
+    /// Parse `timestr` against each of `references` in turn, e.g. an
+    /// event time then an ingestion time, and keep the first result
+    /// that is neither an error nor a DST fold. When no reference gives
+    /// one, the first successful result is returned, or else the error
+    /// of the first reference. Map `parse` over the references instead
+    /// to get every result.
+    pub fn parse_with_references<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        references: &[DateTime<Tz>],
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        let mut fallback = None;
+        for reference in references {
+            let result = self.parse_detailed(timestr, reference);
+            if let Ok(details) = &result
+                && !details.disambiguated()
+            {
+                return Ok(details.datetime);
+            }
+            log::trace!(
+                "Skipping reference {}: {:?}",
+                reference.fixed_offset(),
+                result
+            );
+            fallback = match (fallback, result) {
+                (None | Some(Err(_)), Ok(details)) => Some(Ok(details.datetime)),
+                (None, Err(err)) => Some(Err(err)),
+                (fallback, _) => fallback,
+            };
+        }
+        fallback.unwrap_or_else(|| Err(self.no_match(timestr)))
+    }
+
+    /// Like `parse`, but wall-clock times are placed in the reference's
+    /// own timezone, following its DST rules, rather than the system
+    /// one. The result is in that timezone too.
+    pub fn parse_keep_reference_zone<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<DateTime<Tz>, ParseError> {
+        let parser = self.clone().keep_reference_zone(true);
+        parser
+            .parse(timestr, reference)
+            .map(|dt| dt.with_timezone(&reference.timezone()))
+    }
+
+    /// Like `parse`, but also report how the wall-clock time was
+    /// placed in a timezone.
+    pub fn parse_detailed<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<ParseDetails<'_>, ParseError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("parse", input = timestr).entered();
+        let result = if self.prefer_utc {
+            self.parse_input(timestr, &reference.with_timezone(&Utc))
+        } else {
+            self.parse_input(timestr, reference)
+        };
+        let result = result.and_then(|details| self.finish(details, reference, timestr));
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(details) => tracing::debug!(result = %details.datetime, "parsed"),
+            Err(err) => tracing::debug!(error = %err, "parse failed"),
+        }
+        result
+    }
+
+    fn parse_input<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<ParseDetails<'_>, ParseError> {
+        for Matcher(matcher) in &self.matchers {
+            if let Some(datetime) = matcher.resolve(timestr, &reference.fixed_offset()) {
+                log::trace!("Custom matcher resolved {:?} to {}", timestr, datetime);
+                return Ok(ParseDetails::exact(datetime));
+            }
+        }
+
+        let normalized = self.locale.normalize(timestr);
+        let timestr = &*relative::strip_at(&normalized);
+        if timestr.is_empty() {
+            log::trace!("Using reference: {:?}", reference);
+            return self.resolve(
+                Completed::Local(reference.naive_local()),
+                reference,
+                timestr,
+            );
+        }
+
+        if let Some((word, offset)) = relative::split_keyword(timestr)
+            && let Some(keyword) = self.keywords.iter().find(|k| k.name == word)
+        {
+            let datetime = (keyword.resolve)(&reference.fixed_offset());
+            log::trace!("Keyword {:?} resolved to {}", keyword.name, datetime);
+            if offset.is_empty() {
+                return Ok(ParseDetails::exact(datetime));
+            }
+            let offset =
+                duration::parse_calendar_offset(&offset).ok_or_else(|| self.no_match(timestr))?;
+            return self.apply_calendar_offset(datetime, offset?, reference, timestr);
+        }
+
+        // The rest is read on the wall clock of the zone given
+        if let Some((rest, offset)) = parse::split_utc_offset(timestr) {
+            log::trace!("Reading {:?} in UTC{}", rest, offset);
+            let parser = self.clone().keep_reference_zone(true);
+            // report the format as one of ours, not the copy's
+            return parser
+                .parse_input(rest, &reference.with_timezone(&offset))
+                .map(|details| ParseDetails {
+                    format: details
+                        .format
+                        .and_then(|format| self.formats().find(|own| *own == format)),
+                    ..details
+                });
+        }
+
+        if let Some(naive) =
+            relative::parse_period_start(timestr, reference.naive_local(), self.week_start)
+        {
+            return self.resolve(Completed::Local(naive?), reference, timestr);
+        }
+
+        if let Some(naive) = relative::parse_period_shift(timestr, reference.naive_local()) {
+            return self.resolve(Completed::Local(naive?), reference, timestr);
+        }
+
+        // The anchor is resolved first, then the offset applied to it
+        if let Some((anchor, offset)) = relative::split_anchor(timestr) {
+            let details = match anchor {
+                Anchor::Now => ParseDetails::exact(reference.fixed_offset()),
+                Anchor::Midnight(days) => {
+                    let midnight = relative::shift_periods(
+                        relative::period_start(reference.naive_local(), Unit::Day, self.week_start),
+                        Unit::Day,
+                        days,
+                    )
+                    .ok_or_else(|| ParseError::out_of_range(timestr))?;
+                    self.resolve(Completed::Local(midnight), reference, timestr)?
+                }
+            };
+            log::trace!("Anchor {:?} resolved to {}", anchor, details.datetime);
+            if offset.is_empty() {
+                return Ok(details);
+            }
+            let offset =
+                duration::parse_calendar_offset(&offset).ok_or_else(|| self.no_match(timestr))?;
+            return self.apply_calendar_offset(details.datetime, offset?, reference, timestr);
+        }
+
+        // `tomorrow 15:00`: the time is read on the anchor's day
+        if let Some((days, rest)) = relative::split_day_and_time(timestr) {
+            let day = relative::shift_periods(reference.naive_local(), Unit::Day, days)
+                .ok_or_else(|| ParseError::out_of_range(timestr))?;
+            let day = self
+                .resolve(Completed::Local(day), reference, timestr)?
+                .datetime;
+            log::trace!("Reading {:?} on {}", rest, day.date_naive());
+            return self.parse_input(rest, &day);
+        }
+
+        if let Some(offset) = duration::parse_calendar_offset(timestr) {
+            return self.apply_calendar_offset(
+                reference.fixed_offset(),
+                offset?,
+                reference,
+                timestr,
+            );
+        }
+
+        if let Some(offset) = duration::parse_spoken_offset(timestr) {
+            return apply_offset(reference.fixed_offset(), offset?, timestr);
+        }
+
+        // `@@N`, a date given as days since 1970-01-01
+        if let Some(days) = timestr.strip_prefix("@@") {
+            let days: i64 = days.parse().map_err(|_| self.no_match(timestr))?;
+            let date = chrono::Duration::try_days(days)
+                .and_then(|days| DateTime::UNIX_EPOCH.date_naive().checked_add_signed(days))
+                .ok_or_else(|| ParseError::out_of_range(timestr))?;
+            log::trace!("Day {} since the epoch is {}", days, date);
+            return self.resolve(
+                Completed::Local(date.and_time(chrono::NaiveTime::MIN)),
+                reference,
+                timestr,
+            );
+        }
+
+        // `s<N>`, N seconds after midnight on the reference day
+        if let Some(seconds) = timestr.strip_prefix('s')
+            && !seconds.is_empty()
+            && seconds.bytes().all(|b| b.is_ascii_digit())
+        {
+            let seconds: i64 = seconds
+                .parse()
+                .map_err(|_| ParseError::out_of_range(timestr))?;
+            if seconds >= 86400 && !self.roll_over_seconds_of_day {
+                return Err(ParseError::SecondsOfDayOutOfRange {
+                    input: timestr.to_string(),
+                });
+            }
+            let midnight = reference
+                .naive_local()
+                .date()
+                .and_time(chrono::NaiveTime::MIN);
+            let naive = chrono::Duration::try_seconds(seconds)
+                .and_then(|seconds| midnight.checked_add_signed(seconds))
+                .ok_or_else(|| ParseError::out_of_range(timestr))?;
+            log::trace!("Second {} of the day is {}", seconds, naive);
+            return self.resolve(Completed::Local(naive), reference, timestr);
+        }
+
+        if let Some(elapsed) = subsecond_timestamp(timestr) {
+            log::trace!("Timestamp {:?} read as {}", timestr, elapsed);
+            return apply_offset(self.timestamp_epoch.fixed_offset(), elapsed, timestr);
+        }
+
+        if let Some(seconds) = signed_or_fractional_timestamp(timestr) {
+            // the sign covers the fractional part too: `@-1.5` is -1.5s
+            let seconds = match seconds.strip_prefix(['-', '+']) {
+                Some(_) => duration::parse_offset(&format!("{}s", seconds)),
+                None => Some(duration::parse_duration(&format!("{}s", seconds))),
+            };
+            let seconds = seconds
+                .and_then(Result::ok)
+                .ok_or_else(|| ParseError::out_of_range(timestr))?;
+            return apply_offset(self.timestamp_epoch.fixed_offset(), seconds, timestr);
+        }
+
+        if let Some((qualifier, weekday, rest)) = relative::split_weekday(timestr) {
+            let local = reference.naive_local();
+            let date = relative::resolve_weekday(local.date(), weekday, qualifier, self.week_start)
+                .ok_or_else(|| ParseError::out_of_range(timestr))?;
+            log::trace!("Weekday {:?} resolved to {}", weekday, date);
+            // a bare weekday is midnight of that day
+            if rest.is_empty() {
+                return self.resolve(
+                    Completed::Local(date.and_time(chrono::NaiveTime::MIN)),
+                    reference,
+                    timestr,
+                );
+            }
+            let on_day = |date: chrono::NaiveDate| {
+                let day = self
+                    .resolve(
+                        Completed::Local(date.and_time(local.time())),
+                        reference,
+                        timestr,
+                    )?
+                    .datetime;
+                self.parse_input(rest, &day)
+            };
+            // otherwise the weekday may belong to a format, as in `date` output
+            if let Ok(details) = on_day(date) {
+                // `Fri 17:00` on a Friday evening is next week's, unless
+                // the rest has its own date
+                if qualifier.is_none()
+                    && date == local.date()
+                    && details.datetime < reference.fixed_offset()
+                    && let Some(next_week) = date.checked_add_days(chrono::Days::new(7))
+                    && let Ok(later) = on_day(next_week)
+                    && later.datetime.date_naive() == next_week
+                {
+                    log::trace!("{} has passed, moving to next week", details.datetime);
+                    return Ok(later);
+                }
+                return Ok(details);
+            }
+        }
+
+        if parse::has_misplaced_offset(timestr) {
+            return Err(ParseError::MisplacedOffset {
+                input: timestr.to_string(),
+            });
+        }
+
+        let complete_with_zeroes = self.fill == Fill::Zeroes;
+
+        // Otherwise caught by `%M`
+        if self.bare_number_as_hour
+            && (1..=2).contains(&timestr.len())
+            && timestr.bytes().all(|b| b.is_ascii_digit())
+        {
+            log::trace!("Reading bare number {:?} as an hour", timestr);
+            let completed = parse::complete_partial(timestr, "%H", reference, complete_with_zeroes)
+                .map_err(|_| self.no_match(timestr))?;
+            return self.resolve(completed, reference, timestr);
+        }
+
+        if self.allow_fractional_clock
+            && let Some((hours, fraction)) = timestr.split_once('.')
+            && (1..=2).contains(&hours.len())
+            && !fraction.is_empty()
+            && format!("{}{}", hours, fraction)
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+        {
+            let time = duration::parse_duration(&format!("{}h", timestr))
+                .ok()
+                .filter(|d| *d < chrono::Duration::days(1))
+                .map(|d| chrono::NaiveTime::MIN + d)
+                .ok_or_else(|| self.no_match(timestr))?;
+            log::trace!("Reading fractional hours {:?} as {}", timestr, time);
+            let naive = reference.naive_local().date().and_time(time);
+            return self.resolve(Completed::Local(naive), reference, timestr);
+        }
+
+        // `10:15.5`; with seconds (`10:15:30.5`) the fraction is theirs
+        if self.fractional_minutes
+            && let Some((clock, fraction)) = timestr.split_once('.')
+            && let Some((hours, minutes)) = clock.split_once(':')
+            && (1..=2).contains(&hours.len())
+            && minutes.len() == 2
+            && !fraction.is_empty()
+            && format!("{}{}{}", hours, minutes, fraction)
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+        {
+            let time = chrono::NaiveTime::from_hms_opt(
+                hours.parse().map_err(|_| self.no_match(timestr))?,
+                minutes.parse().map_err(|_| self.no_match(timestr))?,
+                0,
+            )
+            .zip(duration::parse_duration(&format!("0.{}m", fraction)).ok())
+            .map(|(time, seconds)| time + seconds)
+            .ok_or_else(|| self.no_match(timestr))?;
+            log::trace!("Reading fractional minutes {:?} as {}", timestr, time);
+            let naive = reference.naive_local().date().and_time(time);
+            return self.resolve(Completed::Local(naive), reference, timestr);
+        }
+
+        // With an era marker the leading number can only be a year
+        if let Some(astronomical) = parse::strip_era(timestr) {
+            let mut year_first = self.formats().filter(|f| f.starts_with("%Y"));
+            let (format, completed) = year_first
+                .find_map(|format| {
+                    parse::complete_partial(&astronomical, format, reference, complete_with_zeroes)
+                        .ok()
+                        .map(|completed| (format, completed))
+                })
+                .ok_or_else(|| self.no_match(timestr))?;
+            return Ok(ParseDetails {
+                format: Some(format),
+                ..self.resolve(completed, reference, timestr)?
+            });
+        }
+
+        // The fast path reads built-in formats only
+        if self.formats.is_none()
+            && let Some(completed) = parse::complete_iso(timestr, reference, complete_with_zeroes)
+        {
+            log::trace!("Parsed {:?} on the fast path", timestr);
+            #[cfg(feature = "tracing")]
+            tracing::debug!("matched the ISO fast path");
+            let format = if timestr.len() == 10 {
+                "%Y-%m-%d"
+            } else {
+                "%Y-%m-%d %H:%M:%S"
+            };
+            return Ok(ParseDetails {
+                format: Some(format),
+                ..self.resolve(completed, reference, timestr)?
+            });
+        }
+
+        self.first_match(timestr, reference, |items| {
+            parse::complete_items(timestr, items.iter(), reference, complete_with_zeroes)
+        })
+    }
+
+    /// Parse `start..stop` (or `start to stop`, `start->stop`) into its
+    /// bounds. Fields missing from the end
+    /// come from the start. Without separator, the span is the day (or
+    /// `default_span`) following the single time given.
+    pub fn parse_timespan<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        let (start, stop) = match self.anchor_date {
+            Some(date) => {
+                let time = reference.naive_local().time();
+                let anchor = self
+                    .resolve(Completed::Local(date.and_time(time)), reference, timespan)?
+                    .datetime;
+                log::trace!("Timespan anchored on {}", anchor);
+                self.parse_bounds(timespan, &anchor)?
+            }
+            None => self.parse_bounds(timespan, reference)?,
+        };
+
+        if start > stop && self.reversed_span == ReversedSpan::Swap {
+            log::trace!("Swapping reversed timespan {:?}", timespan);
+            return Ok((stop, start));
+        }
+
+        // Validate that start <= stop (reject reverse timespans)
+        if start > stop {
+            return Err(ParseError::ReversedTimespan {
+                input: timespan.to_string(),
+                start,
+                stop,
+            });
+        }
+
+        Ok((start, stop))
+    }
+
+    /// Like `parse_timespan`, but a side of the separator left empty is
+    /// unbounded and returned as `None`: `2025-01-01..` starts on
+    /// 2025-01-01 and never ends, `..2025-01-01` ends there. Inputs with
+    /// both sides, or no separator, give the same bounds as
+    /// `parse_timespan`. `..` alone is an error unless
+    /// `allow_fully_open` is set.
+    pub fn parse_timespan_open<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<OpenTimespan, ParseError> {
+        let open_side = self
+            .split_timespan(timespan)
+            .filter(|(start, stop)| start.trim().is_empty() || stop.trim().is_empty());
+        match open_side {
+            Some((start, stop)) => {
+                let bound = |side: &str| {
+                    let side = side.trim();
+                    (!side.is_empty())
+                        .then(|| self.parse(side, reference))
+                        .transpose()
+                };
+                log::trace!("Open timespan {:?}", timespan);
+                match (bound(start)?, bound(stop)?) {
+                    (None, None) if !self.allow_fully_open => Err(ParseError::FullyOpenTimespan {
+                        input: timespan.to_string(),
+                    }),
+                    bounds => Ok(bounds),
+                }
+            }
+            None => self
+                .parse_timespan(timespan, reference)
+                .map(|(start, stop)| (Some(start), Some(stop))),
+        }
+    }
+
+    /// Parse a list of timespans separated by `,`, `&` or the word
+    /// `and`, e.g. `2025-01-01..2025-01-05 and 2025-02-01..2025-02-05`.
+    ///
+    /// A comma between two digits is a decimal separator, and one
+    /// between a day and a year is part of a date (`Oct 27, 2025`),
+    /// not list separators. The list is only split where every item parses as
+    /// a timespan; if that can be done in more than one way, this
+    /// fails with `ParseError::AmbiguousList`.
+    pub fn parse_timespan_list<Tz: TimeZone>(
+        &self,
+        input: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<Vec<TimespanBounds>, ParseError> {
+        let items = split_list(input);
+        // splits[k]: the ways to read the first k items, counting up to
+        // two, with the first one found
+        let mut splits: Vec<(u8, Vec<TimespanBounds>)> = vec![(1, Vec::new())];
+        for end in 1..=items.len() {
+            let mut ways = (0, Vec::new());
+            for start in 0..end {
+                let (count, spans) = &splits[start];
+                let timespan = input[items[start].start..items[end - 1].end].trim();
+                if *count == 0 || timespan.is_empty() {
+                    continue;
+                }
+                if let Ok(span) = self.parse_timespan(timespan, reference) {
+                    if ways.0 == 0 {
+                        ways.1 = spans.clone();
+                        ways.1.push(span);
+                    }
+                    ways.0 = (ways.0 + count).min(2);
+                }
+            }
+            splits.push(ways);
+        }
+        match splits.pop() {
+            Some((1, spans)) => Ok(spans),
+            Some((2, _)) => Err(ParseError::AmbiguousList {
+                input: input.to_string(),
+            }),
+            // report the first item failing on its own
+            _ => {
+                for item in items {
+                    let timespan = input[item].trim();
+                    if timespan.is_empty() {
+                        return Err(ParseError::EmptyListItem {
+                            input: input.to_string(),
+                        });
+                    }
+                    self.parse_timespan(timespan, reference)?;
+                }
+                unreachable!("each item parses, so the list does")
+            }
+        }
+    }
+
+    fn parse_bounds<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        if let Some((center, radius)) = timespan.split_once(WINDOW_MARKERS) {
+            return self.parse_window(timespan, center, radius, reference);
+        }
+        if let Some((n, unit)) = relative::split_trailing_periods(timespan) {
+            return self.parse_trailing_periods(timespan, n, unit, reference);
+        }
+        if self.dash_as_day_range
+            && let Some((first, last)) = split_day_range(timespan.trim())
+        {
+            return self.parse_day_range(timespan, None, first, last, reference);
+        }
+        if let Some((month, days)) = timespan.trim().split_once(char::is_whitespace)
+            && let Some(month) = self.month_number(month)
+            && let Some((first, last)) = split_day_range(days.trim())
+        {
+            return self.parse_day_range(timespan, Some(month), first, last, reference);
+        }
+        match self.split_timespan(timespan) {
+            Some((start, stop)) => {
+                if let Some(first) = self.locale.parse_weekday(start)
+                    && let Some(last) = self.locale.parse_weekday(stop)
+                {
+                    return self.parse_weekday_range(timespan, first, last, reference);
+                }
+                let first = self.parse(start, reference)?;
+                if let Some(length) = duration::parse_iso(stop.trim()) {
+                    let second = self.add_iso_duration(timespan, &first, length?, reference)?;
+                    return Ok((first, second));
+                }
+                let second = self.parse_end(stop, &first)?;
+                Ok((first, second))
+            }
+            None if self.require_explicit_range => Err(ParseError::MissingSeparator {
+                input: timespan.to_string(),
+                separator: self.separators()[0].to_string(),
+            }),
+            None => {
+                let start = self.parse(timespan, reference)?;
+                if self.span_matches_precision
+                    && let Some(length) = self.input_precision(timespan, reference)
+                {
+                    let stop = self.add_iso_duration(timespan, &start, length, reference)?;
+                    return Ok((start, stop));
+                }
+                let span = self.default_span.unwrap_or(chrono::Duration::days(1));
+                let stop = start
+                    .checked_add_signed(span)
+                    .ok_or_else(|| ParseError::out_of_range(timespan))?;
+                Ok((start, stop))
+            }
+        }
+    }
+
+    // Months are added on the wall clock of the zone times are resolved
+    // in, clamping to the end of shorter months (Jan 31 + 1mo is Feb
+    // 28/29), then the fixed part as a duration
+    fn apply_calendar_offset<Tz: TimeZone>(
+        &self,
+        base: DateTime<FixedOffset>,
+        offset: duration::CalendarDuration,
+        reference: &DateTime<Tz>,
+        timestr: &str,
+    ) -> Result<ParseDetails<'_>, ParseError> {
+        if offset.months == 0 {
+            return apply_offset(base, offset.fixed, timestr);
+        }
+        let wall_clock = parse::wall_clock_for(&base, reference, self.keep_reference_zone);
+        let naive = relative::add_months(wall_clock, offset.months)
+            .ok_or_else(|| ParseError::out_of_range(timestr))?;
+        log::trace!("{} months from {} is {}", offset.months, wall_clock, naive);
+        let shifted = self.resolve(Completed::Local(naive), reference, timestr)?;
+        apply_offset(shifted.datetime, offset.fixed, timestr)
+    }
+
+    // One unit of the least significant field of the first format
+    // reading `timestr`, as `parse` would pick it
+    fn input_precision<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+    ) -> Option<duration::CalendarDuration> {
+        let timestr = &*self.locale.normalize(timestr);
+        let format = self
+            .formats()
+            .find(|format| parse::complete_partial(timestr, format, reference, true).is_ok())?;
+        let precision = format_precision(format);
+        log::trace!(
+            "{:?} read by {:?} with precision {:?}",
+            timestr,
+            format,
+            precision
+        );
+        precision
+    }
+
+    fn separators(&self) -> Vec<&str> {
+        match &self.timespan_separator {
+            Some(separator) => vec![separator],
+            None => TIMESPAN_SEPARATORS.to_vec(),
+        }
+    }
+
+    // Split at the first of the separators found in `timespan`, so
+    // that `10:00 -> 14:00` reads like `10:00->14:00`. Word separators
+    // such as ` to ` only match a whole word, in any case.
+    pub(crate) fn split_timespan<'a>(&self, timespan: &'a str) -> Option<(&'a str, &'a str)> {
+        self.separators()
+            .into_iter()
+            .find_map(|separator| {
+                let word = separator.trim();
+                if !word.is_empty() && word.chars().all(char::is_alphabetic) {
+                    split_word(timespan, word)
+                } else {
+                    timespan.split_once(separator)
+                }
+            })
+            .map(|(start, stop)| (start.trim(), stop.trim()))
+    }
+
+    // `<center> ±<radius>` or `<center>~<radius>`, the span of twice
+    // the radius around center
+    fn parse_window<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        center: &str,
+        radius: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        let center = self.parse(center.trim(), reference)?;
+        let radius = duration::parse_duration(radius.trim())?;
+        if radius <= chrono::Duration::zero() {
+            return Err(ParseError::EmptyWindow {
+                input: timespan.to_string(),
+            });
+        }
+        log::trace!("Window of {} around {}", radius, center);
+        let out_of_range = || ParseError::out_of_range(timespan);
+        Ok((
+            center.checked_sub_signed(radius).ok_or_else(out_of_range)?,
+            center.checked_add_signed(radius).ok_or_else(out_of_range)?,
+        ))
+    }
+
+    // `<start>..P<iso duration>`: the months are added on the wall
+    // clock, clamping to the end of shorter months, then the fixed part
+    // as a duration
+    fn add_iso_duration<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        start: &DateTime<FixedOffset>,
+        length: duration::CalendarDuration,
+        reference: &DateTime<Tz>,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        let out_of_range = || ParseError::out_of_range(timespan);
+        let shifted = if length.months == 0 {
+            *start
+        } else {
+            let naive = relative::add_months(start.naive_local(), length.months)
+                .ok_or_else(out_of_range)?;
+            self.resolve(Completed::Local(naive), reference, timespan)?
+                .datetime
+        };
+        shifted
+            .checked_add_signed(length.fixed)
+            .ok_or_else(out_of_range)
+    }
+
+    // `<first>-<last>` days of `month`, or of the reference month, in
+    // the reference year, both included
+    fn parse_day_range<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        month: Option<u32>,
+        first: &str,
+        last: &str,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        let date = reference.naive_local().date();
+        let month = month.unwrap_or(date.month());
+        let day = |d: &str| {
+            d.parse()
+                .ok()
+                .and_then(|d| NaiveDate::from_ymd_opt(date.year(), month, d))
+                .ok_or_else(|| ParseError::InvalidDayRange {
+                    input: timespan.to_string(),
+                })
+        };
+        let (first, last) = (day(first)?, day(last)?);
+        let end = last
+            .succ_opt()
+            .ok_or_else(|| ParseError::out_of_range(timespan))?;
+        log::trace!("Day range from {} to {}", first, last);
+        Ok((
+            self.midnight(first, reference, timespan)?,
+            self.midnight(end, reference, timespan)?,
+        ))
+    }
+
+    // `<weekday>..<weekday>`, the whole days from the upcoming first
+    // weekday (today if it is that day) to the following last one
+    fn parse_weekday_range<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        first: Weekday,
+        last: Weekday,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        let out_of_range = || ParseError::out_of_range(timespan);
+        let start =
+            relative::resolve_weekday(reference.naive_local().date(), first, None, self.week_start)
+                .ok_or_else(out_of_range)?;
+        let days = u64::from(last.days_since(first)) + 1;
+        let end = start
+            .checked_add_days(Days::new(days))
+            .ok_or_else(out_of_range)?;
+        log::trace!("Weekday range from {} to {}", start, end);
+        Ok((
+            self.midnight(start, reference, timespan)?,
+            self.midnight(end, reference, timespan)?,
+        ))
+    }
+
+    fn midnight<Tz: TimeZone>(
+        &self,
+        date: NaiveDate,
+        reference: &DateTime<Tz>,
+        timespan: &str,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        self.resolve(
+            Completed::Local(date.and_time(chrono::NaiveTime::MIN)),
+            reference,
+            timespan,
+        )
+        .map(|details| details.datetime)
+    }
+
+    // Month named (`oct`, `October`) or numbered (`10`) by `s`
+    fn month_number(&self, s: &str) -> Option<u32> {
+        if let Some(month) = self.locale.parse_month(s) {
+            return Some(month.number_from_month());
+        }
+        s.parse().ok().filter(|m| (1..=12).contains(m))
+    }
+
+    // `last <n> <unit>`, up to the reference. Months and years are
+    // calendar ones, shorter units fixed durations.
+    fn parse_trailing_periods<Tz: TimeZone>(
+        &self,
+        timespan: &str,
+        n: i64,
+        unit: Unit,
+        reference: &DateTime<Tz>,
+    ) -> Result<TimespanBounds, ParseError> {
+        let stop = reference.fixed_offset();
+        let out_of_range = || ParseError::out_of_range(timespan);
+        let start = match unit {
+            Unit::Month | Unit::Year => {
+                let naive = relative::shift_periods(reference.naive_local(), unit, -n)
+                    .ok_or_else(out_of_range)?;
+                self.resolve(Completed::Local(naive), reference, timespan)?
+                    .datetime
+            }
+            Unit::Hour | Unit::Day | Unit::Week => {
+                let naive =
+                    relative::shift_periods(stop.naive_utc(), unit, -n).ok_or_else(out_of_range)?;
+                stop.timezone().from_utc_datetime(&naive)
+            }
+        };
+        log::trace!("Last {} {:?} start at {}", n, unit, start);
+        Ok((start, stop))
+    }
+
+    // The end of a span inherits the fields it lacks above its own
+    // granularity from the start, and zero-fills those below. A bare
+    // number is a minute of the start's hour (`10:15..30`), unless that
+    // would end before the start, in which case it is an hour of the
+    // start's day (`10:30..11` ends at 11:00:00). A number after a colon
+    // is a second of the start's minute (`10:30:45..:50`).
+    fn parse_end(
+        &self,
+        stop: &str,
+        start: &DateTime<FixedOffset>,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        let bare = stop.trim();
+        if let Some(second) = bare.strip_prefix(':')
+            && (1..=2).contains(&second.len())
+            && second.bytes().all(|b| b.is_ascii_digit())
+        {
+            log::trace!("Reading timespan end {:?} as a second", bare);
+            return second
+                .parse()
+                .ok()
+                .and_then(|s| start.with_second(s)?.with_nanosecond(0))
+                .ok_or_else(|| self.no_match(stop));
+        }
+        let end = self.parse_relative_to(stop, start)?;
+        if end < *start && (1..=2).contains(&bare.len()) && bare.bytes().all(|b| b.is_ascii_digit())
+        {
+            log::trace!("Reading timespan end {:?} as an hour", bare);
+            return self.parse_relative_to(&format!("{}h", bare), start);
+        }
+        Ok(end)
+    }
+
+    /// Parse the longest prefix of `timestr` matching one of the
+    /// formats, returning the result with the unparsed remainder.
+    ///
+    /// Formats are all tried; the one consuming the most input wins,
+    /// ties going to the earliest format. Names are not translated
+    /// from the parser's locale here, as the remainder must be a slice
+    /// of the original input.
+    pub fn parse_loose<'a, Tz: TimeZone>(
+        &self,
+        timestr: &'a str,
+        reference: &DateTime<Tz>,
+    ) -> Result<(DateTime<FixedOffset>, &'a str), ParseError> {
+        let complete_with_zeroes = self.fill == Fill::Zeroes;
+        let mut best: Option<(Completed, &str, &str)> = None;
+        for format in self.formats() {
+            log::trace!(
+                "Trying to parse a prefix of {:?} with format {:?}",
+                timestr,
+                format
+            );
+            if let Ok((completed, rest)) =
+                parse::parse_partial_prefix(timestr, format, reference, complete_with_zeroes)
+                && !self.is_clock_like_year(format, &timestr[..timestr.len() - rest.len()])
+                && best
+                    .as_ref()
+                    .is_none_or(|(_, best_rest, _)| rest.len() < best_rest.len())
+            {
+                best = Some((completed, rest, format));
+            }
+        }
+        let (completed, rest, format) = best.ok_or_else(|| self.no_match(timestr))?;
+        let completed = self.apply_year_pivot(completed, format, timestr)?;
+        let completed = self.infer_year(completed, format, reference);
+        let details = self.resolve(completed, reference, timestr)?;
+        let details = self.shift_epoch(details, format, timestr)?;
+        Ok((self.finish(details, reference, timestr)?.datetime, rest))
+    }
+
+    /// See `crate::parse_relative_to`.
+    pub fn parse_relative_to(
+        &self,
+        timestr: &str,
+        anchor: &DateTime<FixedOffset>,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        let timestr = &*self.locale.normalize(timestr);
+        if timestr.is_empty() {
+            return Ok(*anchor);
+        }
+
+        // wall-clock times are placed in the anchor's offset
+        self.clone()
+            .keep_reference_zone(true)
+            .parse_input(timestr, anchor)
+            .and_then(|details| self.finish(details, anchor, timestr))
+            .map(|details| details.datetime)
+    }
+
+    pub fn supported_syntax(&self) -> SupportedSyntax {
+        let units: Vec<&str> = relative::UNIT_NAMES.iter().map(|(n, _)| *n).collect();
+        let qualifiers: Vec<&str> = relative::PERIOD_QUALIFIERS
+            .iter()
+            .map(|(q, _)| *q)
+            .collect();
+        let weekdays: Vec<&str> = relative::WEEKDAYS
+            .iter()
+            .map(|wd| relative::long_weekday_name(*wd))
+            .collect();
+
+        let eras: Vec<&str> = parse::ERAS.iter().map(|(e, _)| *e).collect();
+
+        let anchors: Vec<&str> = relative::ANCHORS.iter().map(|(a, _)| *a).collect();
+        let days: Vec<&str> = relative::ANCHORS
+            .iter()
+            .filter(|(_, a)| matches!(a, Anchor::Midnight(_)))
+            .map(|(a, _)| *a)
+            .collect();
+
+        let mut keywords: Vec<String> = vec!["start".into(), "of".into()];
+        keywords.extend(anchors.iter().map(|a| a.to_string()));
+        keywords.push("at".into());
+        keywords.extend(qualifiers.iter().map(|q| q.to_string()));
+        keywords.extend(units.iter().map(|u| u.to_string()));
+        keywords.extend(weekdays.iter().map(|w| w.to_string()));
+        keywords.extend(eras.iter().map(|e| e.to_string()));
+        keywords.extend(
+            relative::PERIOD_ABBREVIATIONS
+                .iter()
+                .map(|(a, _, _)| a.to_string()),
+        );
+        if self.locale != Locale::ENGLISH {
+            keywords.extend(self.locale.months.iter().map(|m| m.to_string()));
+            keywords.extend(self.locale.weekdays.iter().map(|w| w.to_string()));
+        }
+        keywords.extend(self.keywords.iter().map(|k| k.name.clone()));
+
+        SupportedSyntax {
+            formats: self.formats().map(|f| f.to_string()).collect(),
+            keywords,
+            expressions: vec![
+                format!("start of <{}> <{}>", qualifiers.join("|"), units.join("|")),
+                format!("<next|previous|last> <{}>", units.join("|")),
+                format!("[next|last] <{}> [<time>]", weekdays.join("|")),
+                format!("<year-first format> <{}>", eras.join("|")),
+                "<+|->[<n>y][<n>mo]<duration>".to_string(),
+                "in <duration>".to_string(),
+                "<duration> ago".to_string(),
+                format!("<{}> [<+|-><duration>]", anchors.join("|")),
+                format!("<{}> [at] <time>", days.join("|")),
+                "at <time>".to_string(),
+                "@<hours>:<minutes>[:<seconds>]".to_string(),
+                "@[+|-]<seconds>[.<fraction>]".to_string(),
+                "@<milliseconds>ms".to_string(),
+                "@<microseconds>us".to_string(),
+                "@@[+|-]<days since 1970-01-01>".to_string(),
+                "s<seconds since midnight>".to_string(),
+                "<time> <UTC|GMT>[<+|-><hours>[[:]<minutes>]]".to_string(),
+                format!("<time> {}<duration>", WINDOW_MARKERS[0]),
+                format!("<time>{}<duration>", WINDOW_MARKERS[1]),
+                format!("<time>{}P<ISO 8601 duration>", self.separators()[0]),
+                "<month> <first day>-<last day>".to_string(),
+                format!("<weekday>{}<weekday>", self.separators()[0]),
+                format!(
+                    "<{}> <n> <{}>[s]",
+                    relative::TRAILING_QUALIFIERS.join("|"),
+                    units.join("|")
+                ),
+            ],
+            timespan_separators: self.separators().iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn first_match<Tz: TimeZone>(
+        &self,
+        timestr: &str,
+        reference: &DateTime<Tz>,
+        attempt: impl Fn(&[Item<'static>]) -> ParseResult<Completed>,
+    ) -> Result<ParseDetails<'_>, ParseError> {
+        for (format, items) in self.compiled_formats() {
+            log::trace!("Trying to parse {:?} with format {:?}", timestr, format);
+            if let Ok(completed) = attempt(items)
+                && !self.is_clock_like_year(format, timestr)
+            {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(format, "matched format");
+                let completed = self.apply_year_pivot(completed, format, timestr)?;
+                let completed = self.infer_year(completed, format, reference);
+                let details = ParseDetails {
+                    format: Some(format),
+                    ..self.resolve(completed, reference, timestr)?
+                };
+                return self.shift_epoch(details, format, timestr);
+            }
+        }
+        Err(self.no_match(timestr))
+    }
+
+    fn resolve<Tz: TimeZone>(
+        &self,
+        completed: Completed,
+        reference: &DateTime<Tz>,
+        timestr: &str,
+    ) -> Result<ParseDetails<'_>, ParseError> {
+        match completed {
+            Completed::Instant(dt) => Ok(ParseDetails::exact(dt)),
+            Completed::Local(naive) => {
+                let details = parse::resolve_details(&naive, reference, self.keep_reference_zone)
+                    .ok_or_else(|| {
+                    // no offset is more than a day, so a wall-clock time
+                    // further from the limits is skipped by a DST change
+                    let one_day = chrono::Duration::days(1);
+                    if naive.checked_add_signed(one_day).is_none()
+                        || naive.checked_sub_signed(one_day).is_none()
+                    {
+                        ParseError::out_of_range(timestr)
+                    } else {
+                        ParseError::NonexistentLocal {
+                            input: timestr.to_string(),
+                        }
+                    }
+                })?;
+                self.apply_ambiguous_policy(details, timestr)
+            }
+        }
+    }
+
+    fn apply_ambiguous_policy<'a>(
+        &self,
+        details: ParseDetails<'a>,
+        timestr: &str,
+    ) -> Result<ParseDetails<'a>, ParseError> {
+        let Some(earlier) = details.alternative else {
+            return Ok(details);
+        };
+        match self.ambiguous {
+            AmbiguousPolicy::Earlier => Ok(ParseDetails {
+                datetime: earlier,
+                offset: *earlier.offset(),
+                alternative: Some(details.datetime),
+                ..details
+            }),
+            AmbiguousPolicy::Later => Ok(details),
+            AmbiguousPolicy::Reject => Err(ParseError::AmbiguousLocal {
+                input: timestr.to_string(),
+                earlier,
+                later: details.datetime,
+            }),
+        }
+    }
+
+    // Steps shared by every entry point once the input is resolved
+    fn finish<'a, Tz: TimeZone>(
+        &self,
+        details: ParseDetails<'a>,
+        reference: &DateTime<Tz>,
+        timestr: &str,
+    ) -> Result<ParseDetails<'a>, ParseError> {
+        if self.leap_seconds == LeapSecond::Reject && details.datetime.nanosecond() >= 1_000_000_000
+        {
+            return Err(ParseError::LeapSecond {
+                input: timestr.to_string(),
+            });
+        }
+        Ok(self.postprocess(self.adjust(details, reference)))
+    }
+
+    // Options applying to every result: `floor_at_reference`, then
+    // `whole_seconds`
+    fn adjust<'a, Tz: TimeZone>(
+        &self,
+        details: ParseDetails<'a>,
+        reference: &DateTime<Tz>,
+    ) -> ParseDetails<'a> {
+        let reference = reference.fixed_offset();
+        let mut details = if self.floor_at_reference && details.datetime < reference {
+            log::trace!("Clamping {} up to the reference", details.datetime);
+            ParseDetails {
+                format: details.format,
+                ..ParseDetails::exact(reference)
+            }
+        } else {
+            details
+        };
+        if self.whole_seconds {
+            details.datetime = drop_nanos(details.datetime);
+            details.wall_clock = drop_nanos(details.wall_clock);
+            details.alternative = details.alternative.map(drop_nanos);
+        }
+        details
+    }
+
+    fn postprocess<'a>(&self, mut details: ParseDetails<'a>) -> ParseDetails<'a> {
+        for Postprocessor(f) in &self.postprocessors {
+            let datetime = f(details.datetime);
+            // the wall-clock time moves with the instant
+            details.wall_clock += datetime.signed_duration_since(details.datetime);
+            details.datetime = datetime;
+            details.alternative = details.alternative.map(|dt| f(dt));
+        }
+        details
+    }
+
+    // bare `%Y` numbers outside `MIN_BARE_YEAR..=9999` look like clock times
+    fn is_clock_like_year(&self, format: &str, matched: &str) -> bool {
+        self.formats.is_none()
+            && format == "%Y"
+            && matched
+                .trim()
+                .parse::<u32>()
+                .is_ok_and(|year| !(MIN_BARE_YEAR..=9999).contains(&year))
+    }
+
+    // chrono places `%y` in 1970-2069
+    fn apply_year_pivot(
+        &self,
+        completed: Completed,
+        format: &str,
+        timestr: &str,
+    ) -> Result<Completed, ParseError> {
+        let Completed::Local(naive) = completed else {
+            return Ok(completed);
+        };
+        // `%C%y` is a full year given as century and year of century
+        if !format.contains("%y") || format.contains("%C") {
+            return Ok(completed);
+        }
+        let pivot = self.two_digit_year_pivot.unwrap_or(DEFAULT_YEAR_PIVOT);
+        let year = pivot + (naive.year() - pivot).rem_euclid(100);
+        log::trace!("Two-digit year placed in {}", year);
+        naive
+            .with_year(year)
+            .map(Completed::Local)
+            .ok_or_else(|| ParseError::out_of_range(timestr))
+    }
+
+    // Dates with a month but no year get the reference's year from
+    // `complete_partial`, moved here to the one `year_inference` asks for
+    fn infer_year<Tz: TimeZone>(
+        &self,
+        completed: Completed,
+        format: &str,
+        reference: &DateTime<Tz>,
+    ) -> Completed {
+        let Completed::Local(naive) = completed else {
+            return completed;
+        };
+        let has = |specifiers: &[&str]| specifiers.iter().any(|s| format.contains(s));
+        if self.year_inference == YearInference::Current
+            || !has(&["%m", "%b", "%B"])
+            || has(&["%Y", "%y", "%s", "%+"])
+        {
+            return completed;
+        }
+        let reference = reference.naive_local();
+        let in_year = |offset: i32| naive.with_year(naive.year() + offset);
+        let year = match self.year_inference {
+            YearInference::Current => None,
+            YearInference::Nearest => [-1, 0, 1]
+                .into_iter()
+                .filter_map(in_year)
+                .min_by_key(|candidate| (*candidate - reference).abs()),
+            YearInference::NextFuture => [0, 1]
+                .into_iter()
+                .filter_map(in_year)
+                .find(|candidate| *candidate >= reference),
+        };
+        log::trace!("Year of {} inferred as {:?}", naive, year.map(|y| y.year()));
+        Completed::Local(year.unwrap_or(naive))
+    }
+
+    // chrono reads `%s` as seconds since the Unix epoch
+    fn shift_epoch<'a>(
+        &self,
+        details: ParseDetails<'a>,
+        format: &str,
+        timestr: &str,
+    ) -> Result<ParseDetails<'a>, ParseError> {
+        if !format.contains("%s") || self.timestamp_epoch == DateTime::UNIX_EPOCH {
+            return Ok(details);
+        }
+        details
+            .datetime
+            .checked_add_signed(self.timestamp_epoch - DateTime::UNIX_EPOCH)
+            .map(|datetime| ParseDetails {
+                format: details.format,
+                ..ParseDetails::exact(datetime)
+            })
+            .ok_or_else(|| ParseError::out_of_range(timestr))
+    }
+
+    fn no_match(&self, timestr: &str) -> ParseError {
+        let tried = if self.verbose_errors {
+            self.formats().map(|f| f.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        ParseError::NoFormatMatched {
+            input: timestr.to_string(),
+            tried,
+        }
+    }
+}
+
+// Time since the epoch of an `@` timestamp in milliseconds or
+// microseconds, given by a `ms`/`us` suffix or by the usual 13 or 16
+// digits; `%s` keeps the other lengths as seconds
+fn subsecond_timestamp(timestr: &str) -> Option<chrono::Duration> {
+    let digits = timestr.strip_prefix('@')?;
+    let (digits, micros) = if let Some(digits) = digits.strip_suffix("ms") {
+        (digits, false)
+    } else if let Some(digits) = digits.strip_suffix("us") {
+        (digits, true)
+    } else {
+        match digits.len() {
+            13 => (digits, false),
+            16 => (digits, true),
+            _ => return None,
+        }
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n: i64 = digits.parse().ok()?;
+    if micros {
+        Some(chrono::Duration::microseconds(n))
+    } else {
+        chrono::Duration::try_milliseconds(n)
+    }
+}
+
+// Seconds of an `@` timestamp with a sign or a fractional part, which
+// `%s` doesn't accept
+fn signed_or_fractional_timestamp(timestr: &str) -> Option<&str> {
+    let seconds = timestr.strip_prefix('@')?;
+    let unsigned = seconds.strip_prefix(['-', '+']).unwrap_or(seconds);
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let valid = match unsigned.split_once('.') {
+        Some((int, frac)) => digits(int) && digits(frac),
+        None => digits(unsigned) && unsigned.len() < seconds.len(),
+    };
+    valid.then_some(seconds)
+}
+
+// `<first>-<last>` with one or two digit days
+fn split_day_range(s: &str) -> Option<(&str, &str)> {
+    let (first, last) = s.split_once('-')?;
+    [first, last]
+        .iter()
+        .all(|d| (1..=2).contains(&d.len()) && d.bytes().all(|b| b.is_ascii_digit()))
+        .then_some((first, last))
+}
+
+fn drop_nanos<T: Timelike + Copy>(t: T) -> T {
+    t.with_nanosecond(0).unwrap_or(t)
+}
+
+/// Split `s` around the first whitespace-delimited occurrence of
+/// `word`, ignoring case.
+fn split_word<'a>(s: &'a str, word: &str) -> Option<(&'a str, &'a str)> {
+    s.char_indices().find_map(|(i, _)| {
+        let (before, rest) = s.split_at(i);
+        let after = rest.get(word.len()..)?;
+        (before.ends_with(char::is_whitespace)
+            && after.starts_with(char::is_whitespace)
+            && rest[..word.len()].eq_ignore_ascii_case(word))
+        .then_some((before, after))
+    })
+}
+
+/// Ranges of the items of a timespan list, separators excluded.
+fn split_list(input: &str) -> Vec<Range<usize>> {
+    let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+    let is_decimal = |before: &str, after: &str| {
+        is_digit(before.chars().last()) && is_digit(after.chars().next())
+    };
+    // `<day>, <year>`, as in `Oct 27, 2025`
+    let is_date_year = |before: &str, rest: &str| {
+        let before = before.trim_end();
+        let day = before.len() - before.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let word = rest.trim_start();
+        let digits = word.len() - word.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        (1..=2).contains(&day)
+            && digits == 4
+            && (word[4..].is_empty() || word[4..].starts_with([' ', '.', ',', '&']))
+    };
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let (before, rest) = input.split_at(i);
+        let separator_len = match c {
+            '&' => 1,
+            ',' if !is_decimal(before, &rest[1..]) && !is_date_year(before, &rest[1..]) => 1,
+            'a' | 'A'
+                if before.ends_with(char::is_whitespace)
+                    && rest.get(..3).is_some_and(|w| w.eq_ignore_ascii_case("and"))
+                    && rest[3..].starts_with(char::is_whitespace) =>
+            {
+                3
+            }
+            _ => continue,
+        };
+        items.push(start..i);
+        start = i + separator_len;
+        while chars.next_if(|(j, _)| *j < start).is_some() {}
+    }
+    items.push(start..input.len());
+    items
+}
+
+fn apply_offset(
+    base: DateTime<FixedOffset>,
+    offset: chrono::Duration,
+    timestr: &str,
+) -> Result<ParseDetails<'static>, ParseError> {
+    log::trace!("Offset of {} from {}", offset, base);
+    base.checked_add_signed(offset)
+        .map(ParseDetails::exact)
+        .ok_or_else(|| ParseError::out_of_range(timestr))
+}
+
+// One unit of the least significant field `format` reads
+fn format_precision(format: &str) -> Option<duration::CalendarDuration> {
+    let fixed = |fixed| Some(duration::CalendarDuration { months: 0, fixed });
+    let calendar = |months| {
+        Some(duration::CalendarDuration {
+            months,
+            fixed: chrono::Duration::zero(),
+        })
+    };
+    let specifiers: Vec<char> = format
+        .split('%')
+        .skip(1)
+        .filter_map(|s| s.chars().next())
+        .collect();
+    let has = |set: &str| specifiers.iter().any(|c| set.contains(*c));
+    if has("STs+") {
+        fixed(chrono::Duration::seconds(1))
+    } else if has("MR") {
+        fixed(chrono::Duration::minutes(1))
+    } else if has("HIkl") {
+        fixed(chrono::Duration::hours(1))
+    } else if has("deajF") {
+        fixed(chrono::Duration::days(1))
+    } else if has("mbBh") {
+        calendar(1)
+    } else if has("Yy") {
+        calendar(12)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DateOrder;
+    use chrono::Utc;
+
+    #[test]
+    fn test_start_of_period() {
+        let dt = Utc.with_ymd_and_hms(2025, 12, 17, 9, 10, 11).unwrap(); // a Wednesday
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            Parser::new().parse("start of next month", &dt),
+            Ok(offset.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            Parser::new().parse("start of previous week", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 12, 8, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            Parser::new()
+                .week_start(WeekStart::Sunday)
+                .parse("start of previous week", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 12, 7, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_weekday_and_time() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let at = |d, h, m| offset.with_ymd_and_hms(2025, 10, d, h, m, 0).unwrap();
+        let friday_late = Utc.with_ymd_and_hms(2025, 10, 31, 23, 30, 0).unwrap();
+        let saturday_early = Utc.with_ymd_and_hms(2025, 11, 1, 0, 30, 0).unwrap();
+        let sunday_late = Utc.with_ymd_and_hms(2025, 10, 26, 23, 0, 0).unwrap();
+
+        let parser = Parser::new();
+        // already past on Friday evening: next Friday
+        let next_friday = |h, m| offset.with_ymd_and_hms(2025, 11, 7, h, m, 0).unwrap();
+        assert_eq!(
+            parser.parse("Fri 17:00", &friday_late),
+            Ok(next_friday(17, 0))
+        );
+        assert_eq!(parser.parse("Fri 23:45", &friday_late), Ok(at(31, 23, 45)));
+        assert_eq!(parser.parse("Fri", &friday_late), Ok(at(31, 0, 0)));
+        assert_eq!(
+            parser.parse("Fri 2025-10-31 17:00", &friday_late),
+            Ok(at(31, 17, 0))
+        );
+        assert_eq!(
+            parser.parse("friday 17:00", &saturday_early),
+            Ok(next_friday(17, 0))
+        );
+        assert_eq!(
+            parser.parse("next Mon 09:00", &sunday_late),
+            Ok(at(27, 9, 0))
+        );
+        assert_eq!(parser.parse("last sun 9h", &sunday_late), Ok(at(19, 9, 0)));
+        assert!(parser.parse("Fri toto", &sunday_late).is_err());
+    }
+
+    #[test]
+    fn test_next_and_last() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let at = |m, d, h, mi, s| offset.with_ymd_and_hms(2025, m, d, h, mi, s).unwrap();
+        let wednesday = Utc.with_ymd_and_hms(2025, 10, 22, 9, 10, 11).unwrap();
+        let end_of_march = Utc.with_ymd_and_hms(2025, 3, 31, 9, 10, 11).unwrap();
+
+        let parser = Parser::new();
+        assert_eq!(
+            parser.parse("next friday", &wednesday),
+            Ok(at(10, 31, 0, 0, 0))
+        );
+        assert_eq!(
+            parser.parse("Last Monday", &wednesday),
+            Ok(at(10, 13, 0, 0, 0))
+        );
+        assert_eq!(
+            parser.parse("next wednesday", &wednesday),
+            Ok(at(10, 29, 0, 0, 0))
+        );
+        assert_eq!(
+            parser.parse("next week", &wednesday),
+            Ok(at(10, 29, 9, 10, 11))
+        );
+        assert_eq!(
+            parser.parse("last week", &wednesday),
+            Ok(at(10, 15, 9, 10, 11))
+        );
+        assert_eq!(
+            parser.parse("last month", &wednesday),
+            Ok(at(9, 22, 9, 10, 11))
+        );
+        assert_eq!(
+            parser.parse("next month", &end_of_march),
+            Ok(at(4, 30, 9, 10, 11))
+        );
+        assert_eq!(
+            parser.parse("last month", &end_of_march),
+            Ok(at(2, 28, 9, 10, 11))
+        );
+        assert!(parser.parse("next fortnight", &wednesday).is_err());
+    }
+
+    #[test]
+    fn test_bare_weekday() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let midnight = |m, d| offset.with_ymd_and_hms(2025, m, d, 0, 0, 0).unwrap();
+        let tuesday = Utc.with_ymd_and_hms(2025, 10, 21, 9, 10, 11).unwrap();
+
+        let parser = Parser::new();
+        assert_eq!(parser.parse("friday", &tuesday), Ok(midnight(10, 24)));
+        assert_eq!(parser.parse("FRI", &tuesday), Ok(midnight(10, 24)));
+        assert_eq!(parser.parse("Wed", &tuesday), Ok(midnight(10, 22)));
+        assert_eq!(parser.parse("monday", &tuesday), Ok(midnight(10, 27)));
+        assert_eq!(parser.parse("Tuesday", &tuesday), Ok(midnight(10, 21)));
+        assert_eq!(parser.parse("sunday", &tuesday), Ok(midnight(10, 26)));
+        assert!(parser.parse("fridays", &tuesday).is_err());
+        // numeric inputs are left to the formats
+        assert_eq!(
+            parser.parse("10:00", &tuesday),
+            Ok(offset.with_ymd_and_hms(2025, 10, 21, 10, 0, 0).unwrap())
+        );
+
+        let paris = FixedOffset::east_opt(2 * 3600).unwrap();
+        let reference = paris.with_ymd_and_hms(2025, 10, 21, 9, 10, 11).unwrap();
+        assert_eq!(
+            Parser::new()
+                .keep_reference_zone(true)
+                .parse("thu", &reference),
+            Ok(paris.with_ymd_and_hms(2025, 10, 23, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_at_noise_word() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parser = Parser::new().keep_reference_zone(true);
+        let at = |d, h, m| Utc.with_ymd_and_hms(2025, 10, d, h, m, 0).unwrap();
+
+        assert_eq!(parser.parse("at 14:30", &dt), Ok(at(27, 14, 30).into()));
+        assert_eq!(parser.parse("At 14:30", &dt), parser.parse("14:30", &dt));
+        assert_eq!(
+            parser.parse("tomorrow 15:00", &dt),
+            Ok(at(28, 15, 0).into())
+        );
+        assert_eq!(
+            parser.parse("tomorrow at 15:00", &dt),
+            parser.parse("tomorrow 15:00", &dt)
+        );
+        assert_eq!(
+            parser.parse("yesterday at 9h", &dt),
+            Ok(at(26, 9, 0).into())
+        );
+        assert_eq!(parser.parse("fri at 10:00", &dt), Ok(at(31, 10, 0).into()));
+        assert_eq!(parser.parse("@14:30", &dt), Ok(at(27, 14, 30).into()));
+        assert_eq!(
+            parser.parse("@14:30:15", &dt),
+            Ok(Utc
+                .with_ymd_and_hms(2025, 10, 27, 14, 30, 15)
+                .unwrap()
+                .into())
+        );
+        assert_eq!(
+            parser.parse("@1704150000", &dt),
+            Ok(Utc.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).unwrap().into())
+        );
+        // only the word itself
+        assert!(parser.parse("attoday", &dt).is_err());
+        assert!(parser.parse("14:30 at", &dt).is_err());
+        assert!(parser.parse("tomorrow at", &dt).is_err());
+    }
+
+    #[test]
+    fn test_era() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let pp = |s| format!("{:?}", Parser::new().parse(s, &dt));
+
+        assert_eq!(pp("1 BC"), "Ok(0000-01-01T00:00:00+00:00)");
+        assert_eq!(pp("44 BC"), "Ok(-0043-01-01T00:00:00+00:00)");
+        assert_eq!(pp("1 AD"), "Ok(0001-01-01T00:00:00+00:00)");
+        assert_eq!(pp("2025 AD"), "Ok(2025-01-01T00:00:00+00:00)");
+        assert_eq!(pp("2025-10-27 10:15 AD"), "Ok(2025-10-27T10:15:00+00:00)");
+        assert!(Parser::new().parse("0 BC", &dt).is_err());
+    }
+
+    #[test]
+    fn test_parse_loose() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_loose("2025-10-27 (approx)", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+                " (approx)"
+            ))
+        );
+        assert_eq!(
+            parser.parse_loose("2025-10-27 10:15#!?", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap(),
+                "#!?"
+            ))
+        );
+        assert_eq!(
+            parser.parse_loose("2025-10-27 10:15", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap(),
+                ""
+            ))
+        );
+        assert!(parser.parse_loose("(approx) 2025-10-27", &dt).is_err());
+    }
+
+    #[test]
+    fn test_french_locale() {
+        let dt = Utc.with_ymd_and_hms(2025, 7, 8, 9, 10, 11).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let fr = Parser::new().locale(Locale::from_name("fr_FR").unwrap());
+
+        assert_eq!(
+            fr.parse("27 octobre 2025", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            fr.parse("3 févr.", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            fr.parse("10/12", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 12, 10, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            fr.parse("vendredi 17:00", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 7, 11, 17, 0, 0).unwrap())
+        );
+
+        // the default locale reads slash dates month first
+        assert_eq!(
+            Parser::new().parse("10/12", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 10, 12, 0, 0, 0).unwrap())
+        );
+        assert!(Parser::new().parse("27 octobre 2025", &dt).is_err());
+    }
+
+    #[test]
+    fn test_timestamp_epoch() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let gps = Parser::new().timestamp_epoch(Utc.with_ymd_and_hms(1980, 1, 6, 0, 0, 0).unwrap());
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            gps.parse("@0", &dt),
+            Ok(offset.with_ymd_and_hms(1980, 1, 6, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            gps.parse("@86400", &dt),
+            Ok(offset.with_ymd_and_hms(1980, 1, 7, 0, 0, 0).unwrap())
+        );
+        // only timestamps are affected
+        assert_eq!(
+            gps.parse("2025-10-27", &dt),
+            Ok(offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            Parser::new().parse("@86400", &dt),
+            Ok(offset.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_epoch_days() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(parse("@@0"), Ok("1970-01-01T00:00:00+00:00".to_string()));
+        assert_eq!(
+            parse("@@19658"),
+            Ok("2023-10-28T00:00:00+00:00".to_string())
+        );
+        assert_eq!(parse("@@-1"), Ok("1969-12-31T00:00:00+00:00".to_string()));
+        assert_eq!(parse("@@1"), parse("1970-01-02"));
+        assert!(parse("@@").is_err());
+        assert!(parse("@@1.5").is_err());
+        assert!(parse("@@99999999999999").is_err());
+    }
+
+    #[test]
+    fn test_seconds_of_day() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parse = |parser: &Parser, s| parser.parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse(&Parser::new(), "s43200"),
+            Ok("2025-10-27 12:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&Parser::new(), "s0"),
+            Ok("2025-10-27 00:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&Parser::new(), "s86399"),
+            Ok("2025-10-27 23:59:59 +00:00".to_string())
+        );
+        assert_eq!(
+            Parser::new().parse("s86400", &dt),
+            Err(ParseError::SecondsOfDayOutOfRange {
+                input: "s86400".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&Parser::new().roll_over_seconds_of_day(true), "s90000"),
+            Ok("2025-10-28 01:00:00 +00:00".to_string())
+        );
+        assert!(parse(&Parser::new(), "s").is_err());
+        assert!(parse(&Parser::new(), "s-1").is_err());
+    }
+
+    #[test]
+    fn test_signed_fractional_timestamp() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(
+            parse("@-1.5"),
+            Ok("1969-12-31T23:59:58.500+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@-0.25"),
+            Ok("1969-12-31T23:59:59.750+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@-86400"),
+            Ok("1969-12-31T00:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@1.5"),
+            Ok("1970-01-01T00:00:01.500+00:00".to_string())
+        );
+        assert_eq!(parse("@+2"), Ok("1970-01-01T00:00:02+00:00".to_string()));
+        assert!(parse("@-1.").is_err());
+        assert!(parse("@-.5").is_err());
+        assert!(parse("@-1m2").is_err());
+    }
+
+    #[test]
+    fn test_fractional_timestamp_keeps_nanoseconds() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let parse = |s| {
+            Parser::new().parse(s, &dt).map(|d| {
+                (
+                    d.timestamp(),
+                    d.timestamp_subsec_nanos(),
+                    d.offset().local_minus_utc(),
+                )
+            })
+        };
+
+        assert_eq!(parse("@1704150000.250"), Ok((1704150000, 250_000_000, 0)));
+        assert_eq!(
+            parse("@1704150000.123456789"),
+            Ok((1704150000, 123_456_789, 0))
+        );
+        // digits past the nanosecond are dropped
+        assert_eq!(
+            parse("@1704150000.1234567891"),
+            Ok((1704150000, 123_456_789, 0))
+        );
+        assert_eq!(parse("@1704150000"), Ok((1704150000, 0, 0)));
+    }
+
+    #[test]
+    fn test_subsecond_timestamp() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(
+            parse("@1704150000"),
+            Ok("2024-01-01T23:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@1704150000123"),
+            Ok("2024-01-01T23:00:00.123+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@1704150000123456"),
+            Ok("2024-01-01T23:00:00.123456+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@1500ms"),
+            Ok("1970-01-01T00:00:01.500+00:00".to_string())
+        );
+        assert_eq!(
+            parse("@1704150000000000us"),
+            Ok("2024-01-01T23:00:00+00:00".to_string())
+        );
+        assert!(parse("@ms").is_err());
+        assert!(parse("@12.5ms").is_err());
+        // the epoch is configurable like for seconds
+        let epoch = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Parser::new()
+                .timestamp_epoch(epoch)
+                .parse("@1000ms", &dt)
+                .map(|d| d.to_rfc3339()),
+            Ok("2000-01-01T00:00:01+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_offset_placement() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(
+            parse("2025-10-27T10:15:00+02:00"),
+            Ok("2025-10-27T10:15:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("2025-10-27 +02:00 10:15"),
+            Err(ParseError::MisplacedOffset {
+                input: "2025-10-27 +02:00 10:15".to_string()
+            })
+        );
+        assert!(parse("+0200 10:15").is_err());
+
+        // a leading offset is not after a date or time
+        assert!(!matches!(
+            parse("+12 2025-10-27"),
+            Err(ParseError::MisplacedOffset { .. })
+        ));
+        assert_eq!(
+            Parser::new()
+                .add_format("%z %Y-%m-%d %H:%M")
+                .parse("+0200 2025-10-27 10:15", &dt)
+                .map(|d| d.to_rfc3339()),
+            Ok("2025-10-27T10:15:00+02:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_timespan_anchor_date() {
+        // just before midnight
+        let dt = Utc.with_ymd_and_hms(2025, 10, 26, 23, 59, 0).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 27).unwrap();
+
+        assert_eq!(
+            Parser::new()
+                .anchor_date(date)
+                .parse_timespan("10:00..14:00", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 27, 14, 0, 0).unwrap(),
+            ))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("10:00..14:00", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 26, 10, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 26, 14, 0, 0).unwrap(),
+            ))
+        );
+        // an explicit date wins
+        assert_eq!(
+            Parser::new()
+                .anchor_date(date)
+                .parse_timespan("2025-10-01 10:00..14:00", &dt)
+                .map(|(start, _)| start),
+            Ok(offset.with_ymd_and_hms(2025, 10, 1, 10, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_require_explicit_range() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 1, 6, 0, 0).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            Parser::new().parse_timespan("2025-10-27", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 28, 0, 0, 0).unwrap(),
+            ))
+        );
+        let strict = Parser::new().require_explicit_range(true);
+        assert_eq!(
+            strict.parse_timespan("2025-10-27", &dt),
+            Err(ParseError::MissingSeparator {
+                input: "2025-10-27".to_string(),
+                separator: "..".to_string()
+            })
+        );
+        assert!(strict.parse_timespan("2025-10-27..2025-10-28", &dt).is_ok());
+    }
+
+    #[test]
+    fn test_window_timespan() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 1, 6, 0, 0).unwrap();
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            Parser::new().parse_timespan("2025-10-27 12:00 ±2h", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 27, 14, 0, 0).unwrap(),
+            ))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("12:00±30m", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 1, 11, 30, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 1, 12, 30, 0).unwrap(),
+            ))
+        );
+        assert!(Parser::new().parse_timespan("12:00 ±0s", &dt).is_err());
+        assert!(Parser::new().parse_timespan("12:00 ±", &dt).is_err());
+
+        assert_eq!(
+            Parser::new().parse_timespan("12:00~2h", &dt),
+            Ok((
+                offset.with_ymd_and_hms(2025, 10, 1, 10, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 1, 14, 0, 0).unwrap(),
+            ))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("2025-10-27 12:00 ~ 90m", &dt),
+            Parser::new().parse_timespan("2025-10-27 12:00 ±90m", &dt)
+        );
+        assert!(matches!(
+            Parser::new().parse_timespan("12:00~0s", &dt),
+            Err(ParseError::EmptyWindow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_trailing_periods_timespan() {
+        let dt = Utc.with_ymd_and_hms(2025, 5, 31, 9, 10, 11).unwrap();
+        let span = |s| {
+            Parser::new()
+                .parse_timespan(s, &dt)
+                .map(|(start, stop)| (start.to_string(), stop.to_string()))
+        };
+        let stop = "2025-05-31 09:10:11 +00:00".to_string();
+
+        assert_eq!(
+            span("last 7 days"),
+            Ok(("2025-05-24 09:10:11 +00:00".to_string(), stop.clone()))
+        );
+        assert_eq!(
+            span("past 24 hours"),
+            Ok(("2025-05-30 09:10:11 +00:00".to_string(), stop.clone()))
+        );
+        // Feb 31st is clamped to the end of February
+        assert_eq!(
+            span("previous 3 months"),
+            Ok(("2025-02-28 09:10:11 +00:00".to_string(), stop.clone()))
+        );
+        assert_eq!(
+            span("Last 1 year"),
+            Ok(("2024-05-31 09:10:11 +00:00".to_string(), stop))
+        );
+        assert!(span("last 99999999999 years").is_err());
+    }
+
+    #[test]
+    fn test_iso_duration_end() {
+        let dt = Utc.with_ymd_and_hms(2025, 5, 31, 9, 10, 11).unwrap();
+        let parser = Parser::new().keep_reference_zone(true);
+        let span = |s| {
+            parser
+                .parse_timespan(s, &dt)
+                .map(|(start, stop)| (start.to_string(), stop.to_string()))
+        };
+
+        assert_eq!(
+            span("2025-01-31..P1M"),
+            Ok((
+                "2025-01-31 00:00:00 +00:00".to_string(),
+                "2025-02-28 00:00:00 +00:00".to_string()
+            ))
+        );
+        assert_eq!(
+            span("2024-02-29 12:00..P1Y2MT1H30M"),
+            Ok((
+                "2024-02-29 12:00:00 +00:00".to_string(),
+                "2025-04-29 13:30:00 +00:00".to_string()
+            ))
+        );
+        assert_eq!(
+            span("10:00..PT45M"),
+            Ok((
+                "2025-05-31 10:00:00 +00:00".to_string(),
+                "2025-05-31 10:45:00 +00:00".to_string()
+            ))
+        );
+        assert!(matches!(
+            span("10:00..P1X"),
+            Err(ParseError::InvalidDuration { .. })
+        ));
+        assert_eq!(
+            parser.parse_timespan("2025-05-31 ±PT1H", &dt),
+            Ok((
+                Utc.with_ymd_and_hms(2025, 5, 30, 23, 0, 0)
+                    .unwrap()
+                    .fixed_offset(),
+                Utc.with_ymd_and_hms(2025, 5, 31, 1, 0, 0)
+                    .unwrap()
+                    .fixed_offset()
+            ))
+        );
+        assert!(matches!(
+            parser.parse_timespan("2025-05-31 ±P1M", &dt),
+            Err(ParseError::CalendarDuration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_weekday_range() {
+        // a Wednesday
+        let dt = Utc.with_ymd_and_hms(2025, 10, 22, 9, 10, 11).unwrap();
+        let day = |m, d| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, m, d, 0, 0, 0)
+                .unwrap()
+        };
+
+        assert_eq!(
+            Parser::new().parse_timespan("Mon..Fri", &dt),
+            Ok((day(10, 27), day(11, 1)))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("Fri..Mon", &dt),
+            Ok((day(10, 24), day(10, 28)))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("monday to Friday", &dt),
+            Ok((day(10, 27), day(11, 1)))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("wed..tues.", &dt),
+            Ok((day(10, 22), day(10, 29)))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("Sun..Sun", &dt),
+            Ok((day(10, 26), day(10, 27)))
+        );
+        assert_eq!(
+            Parser::new()
+                .locale(Locale::FRENCH)
+                .parse_timespan("lundi..vendredi", &dt),
+            Ok((day(10, 27), day(11, 1)))
+        );
+        // with a time, the side is a time like any other
+        assert_eq!(
+            Parser::new().parse_timespan("Mon 10:00..Fri", &dt),
+            Ok((
+                Utc.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap().into(),
+                day(10, 31)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_dash_as_day_range() {
+        let dt = Utc.with_ymd_and_hms(2025, 2, 20, 9, 10, 11).unwrap();
+        let day = |m, d| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, m, d, 0, 0, 0)
+                .unwrap()
+        };
+        let days = Parser::new().dash_as_day_range(true);
+
+        assert_eq!(
+            days.parse_timespan("1-12", &dt),
+            Ok((day(2, 1), day(2, 13)))
+        );
+        assert_eq!(
+            days.parse_timespan("20-28", &dt),
+            Ok((day(2, 20), day(3, 1)))
+        );
+        assert!(days.parse_timespan("12-1", &dt).is_err());
+        assert!(days.parse_timespan("1-30", &dt).is_err());
+        assert_eq!(
+            days.parse_timespan("2025-03-01", &dt),
+            Ok((day(3, 1), day(3, 2)))
+        );
+        assert_eq!(
+            Parser::new().parse_timespan("1-12", &dt),
+            Ok((day(1, 12), day(1, 13)))
+        );
+    }
+
+    #[test]
+    fn test_month_day_range() {
+        let dt = Utc.with_ymd_and_hms(2025, 2, 20, 9, 10, 11).unwrap();
+        let day = |m, d| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, m, d, 0, 0, 0)
+                .unwrap()
+        };
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_timespan("Oct 1-5", &dt),
+            Ok((day(10, 1), day(10, 6)))
+        );
+        assert_eq!(
+            parser.parse_timespan("October 10-12", &dt),
+            Ok((day(10, 10), day(10, 13)))
+        );
+        assert_eq!(
+            parser.parse_timespan("oct 9-31", &dt),
+            Ok((day(10, 9), day(11, 1)))
+        );
+        assert_eq!(
+            parser.parse_timespan("10 1-5", &dt),
+            Ok((day(10, 1), day(10, 6)))
+        );
+        assert_eq!(
+            Parser::new()
+                .locale(Locale::FRENCH)
+                .parse_timespan("octobre 1-5", &dt),
+            Ok((day(10, 1), day(10, 6)))
+        );
+        assert_eq!(
+            parser.parse_timespan("Feb 27-30", &dt),
+            Err(ParseError::InvalidDayRange {
+                input: "Feb 27-30".to_string()
+            })
+        );
+        assert!(parser.parse_timespan("Oct 5-1", &dt).is_err());
+        assert!(parser.parse_timespan("13 1-5", &dt).is_err());
+    }
+
+    #[test]
+    fn test_configured_timespan() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let at = |h| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, 10, 27, h, 0, 0)
+                .unwrap()
+        };
+        let parser = Parser::new()
+            .timespan_separator(" to ")
+            .reversed_span(ReversedSpan::Swap)
+            .default_span(chrono::Duration::hours(1));
+
+        assert_eq!(
+            parser.parse_timespan("10:00 to 14:00", &dt),
+            Ok((at(10), at(14)))
+        );
+        assert_eq!(
+            parser.parse_timespan("14:00 to 10:00", &dt),
+            Ok((at(10), at(14)))
+        );
+        assert_eq!(parser.parse_timespan("10:00", &dt), Ok((at(10), at(11))));
+        assert!(parser.parse_timespan("10:00..14:00", &dt).is_err());
+        assert_eq!(
+            parser.supported_syntax().timespan_separators,
+            vec![" to ".to_string()]
+        );
+        assert!(
+            Parser::new()
+                .timespan_separator(" - ")
+                .parse_timespan("10:00 to 14:00", &dt)
+                .is_err()
+        );
+
+        for input in [
+            "10:00..14:00",
+            "10:00 to 14:00",
+            "10:00->14:00",
+            "10:00 -> 14:00",
+        ] {
+            assert_eq!(
+                Parser::new().parse_timespan(input, &dt),
+                Ok((at(10), at(14))),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            Parser::new().supported_syntax().timespan_separators,
+            vec!["..".to_string(), " to ".to_string(), "->".to_string()]
+        );
+
+        assert!(Parser::new().parse_timespan("14:00..10:00", &dt).is_err());
+        assert_eq!(
+            Parser::new()
+                .timespan_separator(" to ")
+                .require_explicit_range(true)
+                .parse_timespan("10:00", &dt),
+            Err(ParseError::MissingSeparator {
+                input: "10:00".to_string(),
+                separator: " to ".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_separator() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let day = |d| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, 1, d, 0, 0, 0)
+                .unwrap()
+        };
+        let parse = |s| Parser::new().parse_timespan(s, &dt);
+
+        for input in [
+            "2025-01-01 to 2025-01-03",
+            "2025-01-01 TO 2025-01-03",
+            "2025-01-01\tto  2025-01-03",
+        ] {
+            assert_eq!(parse(input), Ok((day(1), day(3))), "{input:?}");
+        }
+        assert_eq!(
+            split_word("10:00 to 14:00", "to"),
+            Some(("10:00 ", " 14:00"))
+        );
+        assert_eq!(split_word("10:00 today", "to"), None);
+        assert_eq!(split_word("auto to", "to"), None);
+        assert!(parse("2025-01-01 toward 2025-01-03").is_err());
+    }
+
+    #[test]
+    fn test_open_timespan() {
+        let dt = Utc.with_ymd_and_hms(2025, 5, 31, 9, 10, 11).unwrap();
+        let parser = Parser::new().keep_reference_zone(true);
+        let new_year = Some(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+        );
+
+        assert_eq!(
+            parser.parse_timespan_open("2025-01-01..", &dt),
+            Ok((new_year, None))
+        );
+        assert_eq!(
+            parser.parse_timespan_open(".. 2025-01-01", &dt),
+            Ok((None, new_year))
+        );
+        assert_eq!(
+            parser.parse_timespan_open("..", &dt),
+            Err(ParseError::FullyOpenTimespan {
+                input: "..".to_string()
+            })
+        );
+        assert_eq!(
+            parser
+                .clone()
+                .allow_fully_open(true)
+                .parse_timespan_open(" .. ", &dt),
+            Ok((None, None))
+        );
+        assert_eq!(
+            parser.parse_timespan_open("2025-01-01..2025-01-02", &dt),
+            parser
+                .parse_timespan("2025-01-01..2025-01-02", &dt)
+                .map(|(start, stop)| (Some(start), Some(stop)))
+        );
+        assert_eq!(
+            parser.parse_timespan_open("2025-01-01", &dt),
+            parser
+                .parse_timespan("2025-01-01", &dt)
+                .map(|(start, stop)| (Some(start), Some(stop)))
+        );
+        assert!(parser.parse_timespan_open("garbage..", &dt).is_err());
+        // `parse_timespan` still reads an empty end as the start itself
+        let closed = parser.parse_timespan("2025-01-01..", &dt).unwrap();
+        assert_eq!((Some(closed.0), Some(closed.1)), (new_year, new_year));
+    }
+
+    #[test]
+    fn test_span_matches_precision() {
+        let dt = Utc.with_ymd_and_hms(2025, 5, 31, 9, 10, 11).unwrap();
+        let parser = Parser::new()
+            .keep_reference_zone(true)
+            .span_matches_precision(true);
+        let length = |s| {
+            parser
+                .parse_timespan(s, &dt)
+                .map(|(start, stop)| (stop - start).to_string())
+        };
+
+        assert_eq!(length("10:15:30"), Ok("PT1S".to_string()));
+        assert_eq!(length("10:15"), Ok("PT60S".to_string()));
+        assert_eq!(length("2025-10-27 10"), Ok("PT3600S".to_string()));
+        assert_eq!(length("2025-01-01"), Ok("PT86400S".to_string()));
+        assert_eq!(length("2025-02"), Ok("PT2419200S".to_string()));
+        assert_eq!(length("2024"), Ok("PT31622400S".to_string()));
+        assert_eq!(length("@1761556211"), Ok("PT1S".to_string()));
+        // not read by a format
+        assert_eq!(length("tomorrow"), Ok("PT86400S".to_string()));
+        assert_eq!(
+            parser
+                .clone()
+                .default_span(chrono::Duration::hours(2))
+                .parse_timespan("now", &dt),
+            Ok((
+                dt.fixed_offset(),
+                (dt + chrono::Duration::hours(2)).fixed_offset()
+            ))
+        );
+
+        // off by default
+        assert_eq!(
+            Parser::new()
+                .parse_timespan("10:15", &dt)
+                .map(|(start, stop)| stop - start),
+            Ok(chrono::Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn test_timespan_list() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 1, 6, 0, 0).unwrap();
+        let day = |m, d| {
+            FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, m, d, 0, 0, 0)
+                .unwrap()
+        };
+        let parse = |s| Parser::new().parse_timespan_list(s, &dt);
+
+        let expected = Ok(vec![(day(1, 1), day(1, 5)), (day(2, 1), day(2, 5))]);
+        assert_eq!(
+            parse("2025-01-01..2025-01-05 and 2025-02-01..2025-02-05"),
+            expected
+        );
+        assert_eq!(
+            parse("2025-01-01..2025-01-05 & 2025-02-01..2025-02-05"),
+            expected
+        );
+        assert_eq!(
+            parse("2025-01-01..2025-01-05, 2025-02-01..2025-02-05"),
+            expected
+        );
+        assert_eq!(
+            parse("2025-01-01..2025-01-05&2025-02-01..2025-02-05 AND 2025-03-01"),
+            Ok(vec![
+                (day(1, 1), day(1, 5)),
+                (day(2, 1), day(2, 5)),
+                (day(3, 1), day(3, 2)),
+            ])
+        );
+        assert!(parse("2025-01-01..2025-01-05 and").is_err());
+        assert!(parse("2025-01-01, , 2025-02-01").is_err());
+
+        // a comma before a year is part of the date
+        assert_eq!(
+            parse("Oct 27, 2025..Oct 28, 2025"),
+            Ok(vec![(day(10, 27), day(10, 28))])
+        );
+        assert_eq!(
+            parse("Oct 27, 2025..Oct 28, 2025 and 2025-02-01..2025-02-05"),
+            Ok(vec![(day(10, 27), day(10, 28)), (day(2, 1), day(2, 5))])
+        );
+        // the day name can be read on its own or as part of the date
+        assert_eq!(
+            Parser::new()
+                .with_formats(&["%a", "%d %b %Y", "%a, %d %b %Y"])
+                .parse_timespan_list("Mon, 27 Oct 2025", &dt),
+            Err(ParseError::AmbiguousList {
+                input: "Mon, 27 Oct 2025".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_list_guards() {
+        let split_list = |s: &'static str| {
+            split_list(s)
+                .into_iter()
+                .map(|r| s[r].trim())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(split_list("10:00..12:00"), ["10:00..12:00"]);
+        assert_eq!(split_list("10:00:00,5..11:00"), ["10:00:00,5..11:00"]);
+        assert_eq!(split_list("1, 2"), ["1", "2"]);
+        assert_eq!(split_list("sandwich..band"), ["sandwich..band"]);
+        assert_eq!(split_list("a and b"), ["a", "b"]);
+        assert_eq!(split_list("Oct 27, 2025"), ["Oct 27, 2025"]);
+        assert_eq!(split_list("2024, 2025-01-01"), ["2024", "2025-01-01"]);
+        assert_eq!(split_list("2024, 2025"), ["2024", "2025"]);
+    }
+
+    #[test]
+    fn test_multi_unit_offset() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse("+1d12h"),
+            Ok("2025-10-28 21:10:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("-2h30m"),
+            Ok("2025-10-27 06:40:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("+30m2h"),
+            Err(ParseError::InvalidOffset {
+                input: "+30m2h".to_string()
+            })
+        );
+        assert!(parse("+1h1h").is_err());
+    }
+
+    #[test]
+    fn test_negative_year_is_not_an_offset() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse("-0043-01-01"),
+            Ok("-0043-01-01 00:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("+2025-10-27 10:00"),
+            Ok("2025-10-27 10:00:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keep_reference_zone() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let parser = Parser::new();
+
+        let utc = Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        let dt = parser.parse_keep_reference_zone("2025-07-01 10:00", &utc);
+        assert_eq!(dt, Ok(Utc.with_ymd_and_hms(2025, 7, 1, 10, 0, 0).unwrap()));
+
+        let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+        let reference = eastern.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        assert_eq!(
+            parser
+                .parse("2025-07-01 10:00", &reference)
+                .map(|d| d.to_string()),
+            Ok("2025-07-01 10:00:00 +02:00".to_string())
+        );
+        assert_eq!(
+            parser
+                .parse_keep_reference_zone("2025-07-01 10:00", &reference)
+                .map(|d| d.to_string()),
+            Ok("2025-07-01 10:00:00 -05:00".to_string())
+        );
+        assert_eq!(
+            parser
+                .parse_keep_reference_zone("fri 10:00", &reference)
+                .map(|d| d.to_string()),
+            Ok("2025-01-17 10:00:00 -05:00".to_string())
+        );
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_keep_reference_zone_follows_named_zone_dst() {
+        let reference = chrono_tz::America::New_York
+            .with_ymd_and_hms(2025, 1, 15, 9, 0, 0)
+            .unwrap();
+        let parse = |s| {
+            Parser::new()
+                .parse_keep_reference_zone(s, &reference)
+                .map(|d| d.to_rfc3339())
+        };
+        assert_eq!(
+            parse("2025-07-01 10:00"),
+            Ok("2025-07-01T10:00:00-04:00".to_string())
+        );
+        assert_eq!(
+            parse("2025-12-01 10:00"),
+            Ok("2025-12-01T10:00:00-05:00".to_string())
+        );
+        assert_eq!(parse("10:00"), Ok("2025-01-15T10:00:00-05:00".to_string()));
+    }
+
+    #[test]
+    fn test_parse_with_references() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let paris = FixedOffset::east_opt(3600).unwrap();
+        let event = paris.with_ymd_and_hms(2025, 3, 30, 12, 0, 0).unwrap();
+        let ingestion = paris.with_ymd_and_hms(2025, 3, 31, 8, 0, 0).unwrap();
+        let fold = paris.with_ymd_and_hms(2025, 10, 26, 12, 0, 0).unwrap();
+        let parser = Parser::new();
+        let parse = |s, references: &[DateTime<FixedOffset>]| {
+            parser
+                .parse_with_references(s, references)
+                .map(|dt| dt.to_rfc3339())
+        };
+
+        // both parse, the first reference wins
+        assert_eq!(
+            parse("10:00", &[event, ingestion]),
+            Ok("2025-03-30T10:00:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("10:00", &[ingestion, event]),
+            Ok("2025-03-31T10:00:00+02:00".to_string())
+        );
+        // 02:30 is skipped on the event day (DST gap) and repeated on
+        // the fold day
+        assert_eq!(
+            parse("02:30", &[event, ingestion]),
+            Ok("2025-03-31T02:30:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("02:30", &[fold, ingestion]),
+            Ok("2025-03-31T02:30:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("02:30", &[event, fold]),
+            Ok("2025-10-26T02:30:00+01:00".to_string())
+        );
+        assert_eq!(
+            parse("02:30", &[event]),
+            Err(ParseError::NonexistentLocal {
+                input: "02:30".to_string()
+            })
+        );
+        assert!(matches!(
+            parse("someday", &[event, ingestion]),
+            Err(ParseError::NoFormatMatched { .. })
+        ));
+        assert!(matches!(
+            parse("10:00", &[]),
+            Err(ParseError::NoFormatMatched { .. })
+        ));
+    }
+
+    #[test]
+    fn test_two_digit_year_pivot() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let year = |parser: Parser, s| parser.parse(s, &dt).map(|d| d.year());
+
+        assert_eq!(year(Parser::new(), "10/27/50"), Ok(2050));
+        assert_eq!(year(Parser::new(), "10/27/69"), Ok(1969));
+        assert_eq!(year(Parser::new(), "10/27/68"), Ok(2068));
+        assert_eq!(
+            year(Parser::new().two_digit_year_pivot(1900), "10/27/50"),
+            Ok(1950)
+        );
+        assert_eq!(
+            year(Parser::new().two_digit_year_pivot(1900), "10/27/05"),
+            Ok(1905)
+        );
+        assert_eq!(
+            year(Parser::new().locale(Locale::FRENCH), "27/10/50"),
+            Ok(2050)
+        );
+        assert_eq!(
+            year(Parser::new().locale(Locale::FRENCH), "27/10/2025"),
+            Ok(2025)
+        );
+        // no Feb 29 in 1900
+        assert!(
+            Parser::new()
+                .two_digit_year_pivot(1900)
+                .parse("02/29/00", &dt)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_policy() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let winter = FixedOffset::east_opt(3600).unwrap();
+        let summer = FixedOffset::east_opt(2 * 3600).unwrap();
+        let reference = winter.with_ymd_and_hms(2025, 12, 1, 12, 0, 0).unwrap();
+        let earlier = summer.with_ymd_and_hms(2025, 10, 26, 2, 30, 0).unwrap();
+        let later = winter.with_ymd_and_hms(2025, 10, 26, 2, 30, 0).unwrap();
+        let fold = "2025-10-26 02:30";
+
+        let pick = |parser: Parser| {
+            let details = parser.parse_detailed(fold, &reference).unwrap();
+            (details.datetime, details.offset, details.alternative)
+        };
+        assert_eq!(pick(Parser::new()), (later, winter, Some(earlier)));
+        assert_eq!(
+            pick(Parser::new().ambiguous(AmbiguousPolicy::Earlier)),
+            (earlier, summer, Some(later))
+        );
+        assert_eq!(
+            pick(Parser::new().ambiguous(AmbiguousPolicy::Later)),
+            (later, winter, Some(earlier))
+        );
+
+        let reject = Parser::new().ambiguous(AmbiguousPolicy::Reject);
+        assert_eq!(
+            reject.parse(fold, &reference),
+            Err(ParseError::AmbiguousLocal {
+                input: fold.to_string(),
+                earlier,
+                later
+            })
+        );
+        assert_eq!(
+            reject.try_parse(fold, &reference),
+            ParseOutcome::Ambiguous(vec![earlier, later])
+        );
+        // unambiguous times are unaffected
+        assert_eq!(
+            reject.parse("2025-10-26 04:30", &reference),
+            Ok(winter.with_ymd_and_hms(2025, 10, 26, 4, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_try_parse_outcomes() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let parser = Parser::new();
+        let winter = FixedOffset::east_opt(3600).unwrap();
+        let reference = winter.with_ymd_and_hms(2025, 12, 1, 12, 0, 0).unwrap();
+        let summer = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(
+            parser.try_parse("2025-12-02 10:00", &reference),
+            ParseOutcome::Parsed(winter.with_ymd_and_hms(2025, 12, 2, 10, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parser.try_parse("2025-10-26 02:30", &reference),
+            ParseOutcome::Ambiguous(vec![
+                summer.with_ymd_and_hms(2025, 10, 26, 2, 30, 0).unwrap(),
+                winter.with_ymd_and_hms(2025, 10, 26, 2, 30, 0).unwrap(),
+            ])
+        );
+        assert_eq!(
+            parser.try_parse("2025-03-30 02:30", &reference),
+            ParseOutcome::NonexistentLocal
+        );
+        assert_eq!(parser.try_parse("toto", &reference), ParseOutcome::NoMatch);
+        assert_eq!(
+            parser.try_parse("2025-02-30", &reference),
+            ParseOutcome::NoMatch
+        );
+
+        let max = DateTime::<Utc>::MAX_UTC;
+        assert_eq!(parser.try_parse("tomorrow", &max), ParseOutcome::OutOfRange);
+    }
+
+    #[test]
+    fn test_period_abbreviations() {
+        let dt = Utc.with_ymd_and_hms(2025, 12, 27, 9, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(parse("eom"), Ok("2026-01-01 00:00:00 +00:00".to_string()));
+        assert_eq!(parse("boy"), Ok("2025-01-01 00:00:00 +00:00".to_string()));
+        assert_eq!(parse("EOD"), Ok("2025-12-28 00:00:00 +00:00".to_string()));
+        assert_eq!(parse("sow"), Ok("2025-12-22 00:00:00 +00:00".to_string()));
+        assert_eq!(parse("eow"), Ok("2025-12-29 00:00:00 +00:00".to_string()));
+        assert_eq!(
+            Parser::new()
+                .week_start(WeekStart::Sunday)
+                .parse("EOW", &dt)
+                .map(|d| d.to_string()),
+            Ok("2025-12-28 00:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            Parser::new()
+                .parse_timespan("bom..eom", &dt)
+                .map(|(start, stop)| (start.to_string(), stop.to_string())),
+            Ok((
+                "2025-12-01 00:00:00 +00:00".to_string(),
+                "2026-01-01 00:00:00 +00:00".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_relative_to_keeps_offset() {
+        let anchor = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2014, 7, 8, 11, 10, 11)
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", Parser::new().parse_relative_to("12h", &anchor)),
+            "Ok(2014-07-08T12:00:00+02:00)"
+        );
+        assert_eq!(
+            format!("{:?}", Parser::new().parse_relative_to("@0", &anchor)),
+            "Ok(1970-01-01T00:00:00+00:00)"
+        );
+    }
+
+    #[test]
+    fn test_timespan_end_keeps_start_offset() {
+        unsafe {
+            std::env::set_var("TZ", "Europe/Paris");
+        }
+        let dt = Utc.with_ymd_and_hms(2025, 10, 1, 6, 0, 0).unwrap();
+        let anchor = FixedOffset::east_opt(5 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 27, 10, 0, 0)
+            .unwrap();
+        let relative = |s| {
+            Parser::new()
+                .parse_relative_to(s, &anchor)
+                .map(|dt| dt.to_rfc3339())
+        };
+        let end = |s| {
+            Parser::new()
+                .parse_timespan(s, &dt)
+                .map(|(_, stop)| stop.to_rfc3339())
+        };
+
+        assert_eq!(
+            relative("12:00"),
+            Ok("2025-10-27T12:00:00+05:00".to_string())
+        );
+        assert_eq!(
+            end("2025-10-27 10:00 +05:00..12:00"),
+            Ok("2025-10-27T12:00:00+05:00".to_string())
+        );
+        assert_eq!(
+            end("2025-10-27 10:00 +05:00..30"),
+            Ok("2025-10-27T10:30:00+05:00".to_string())
+        );
+        assert_eq!(
+            end("2025-10-27 10:00 +05:00..+1h"),
+            Ok("2025-10-27T11:00:00+05:00".to_string())
+        );
+
+        // the whole grammar, not only the formats
+        for (input, expected) in [
+            ("+1h", "2025-10-27T11:00:00+05:00"),
+            ("now", "2025-10-27T10:00:00+05:00"),
+            ("tomorrow", "2025-10-28T00:00:00+05:00"),
+            ("fri 09:00", "2025-10-31T09:00:00+05:00"),
+            ("in 15m", "2025-10-27T10:15:00+05:00"),
+        ] {
+            assert_eq!(relative(input), Ok(expected.to_string()), "{input}");
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_events() {
+        use std::fmt::Write as _;
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        struct Fields<'a>(&'a mut String);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                let _ = write!(self.0, " {}={:?}", field.name(), value);
+            }
+        }
+
+        // Records spans and events as `<name> <field>=<value>...` lines
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl tracing::Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut line = span.metadata().name().to_string();
+                span.record(&mut Fields(&mut line));
+                self.0.lock().unwrap().push(line);
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut line = String::from("event");
+                event.record(&mut Fields(&mut line));
+                self.0.lock().unwrap().push(line);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let recorder = Recorder::default();
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            Parser::new().parse("10:15", &dt).unwrap();
+        });
+
+        let lines = recorder.0.lock().unwrap();
+        assert_eq!(lines[0], "parse input=\"10:15\"");
+        assert!(lines.contains(&"event message=matched format format=\"%H:%M\"".to_string()));
+        assert!(lines.iter().any(|l| l.contains("resolved wall-clock time")
+            && l.contains("offset=+00:00")
+            && l.contains("zone=\"utc\"")));
+        assert_eq!(
+            lines.last().unwrap(),
+            "event message=parsed result=2025-10-27 10:15:00 +00:00"
+        );
+    }
+
+    #[test]
+    fn test_year_inference() {
+        let new_year = Utc.with_ymd_and_hms(2026, 1, 2, 9, 10, 11).unwrap();
+        let christmas_eve = Utc.with_ymd_and_hms(2025, 12, 24, 9, 10, 11).unwrap();
+        let parse = |inference, s, reference: &DateTime<Utc>| {
+            Parser::new()
+                .keep_reference_zone(true)
+                .year_inference(inference)
+                .parse(s, reference)
+                .map(|dt| dt.date_naive().to_string())
+        };
+        let date = |s: &str| Ok(s.to_string());
+
+        assert_eq!(
+            parse(YearInference::Current, "12-25", &new_year),
+            date("2026-12-25")
+        );
+        assert_eq!(
+            parse(YearInference::Nearest, "12-25", &new_year),
+            date("2025-12-25")
+        );
+        assert_eq!(
+            parse(YearInference::NextFuture, "12-25", &new_year),
+            date("2026-12-25")
+        );
+
+        assert_eq!(
+            parse(YearInference::Current, "01-03", &christmas_eve),
+            date("2025-01-03")
+        );
+        assert_eq!(
+            parse(YearInference::Nearest, "01-03", &christmas_eve),
+            date("2026-01-03")
+        );
+        assert_eq!(
+            parse(YearInference::NextFuture, "01-03 10:00", &christmas_eve),
+            date("2026-01-03")
+        );
+        assert_eq!(
+            parse(YearInference::NextFuture, "12-24 10:00", &christmas_eve),
+            date("2025-12-24")
+        );
+
+        // parse_loose too
+        assert_eq!(
+            Parser::new()
+                .keep_reference_zone(true)
+                .year_inference(YearInference::Nearest)
+                .parse_loose("12-25 rest", &new_year)
+                .map(|(dt, rest)| (dt.date_naive().to_string(), rest)),
+            Ok(("2025-12-25".to_string(), " rest"))
+        );
+
+        // an explicit year, or no month, is left alone
+        assert_eq!(
+            parse(YearInference::Nearest, "2026-12-25", &new_year),
+            date("2026-12-25")
+        );
+        assert_eq!(
+            parse(YearInference::Nearest, "10:00", &new_year),
+            date("2026-01-02")
+        );
+    }
+
+    #[test]
+    fn test_calendar_offset() {
+        let jan31 = Utc.with_ymd_and_hms(2024, 1, 31, 9, 10, 11).unwrap();
+        let leap_day = Utc.with_ymd_and_hms(2024, 2, 29, 9, 10, 11).unwrap();
+        let parser = Parser::new().keep_reference_zone(true);
+        let utc = |y, mo, d, h, mi, s| {
+            Ok(Utc
+                .with_ymd_and_hms(y, mo, d, h, mi, s)
+                .unwrap()
+                .fixed_offset())
+        };
+
+        // clamped to the end of February
+        assert_eq!(parser.parse("+1mo", &jan31), utc(2024, 2, 29, 9, 10, 11));
+        assert_eq!(parser.parse("+1y", &leap_day), utc(2025, 2, 28, 9, 10, 11));
+        assert_eq!(parser.parse("-1mo", &jan31), utc(2023, 12, 31, 9, 10, 11));
+        assert_eq!(
+            parser.parse("now +1y1mo2h", &jan31),
+            utc(2025, 2, 28, 11, 10, 11)
+        );
+        assert_eq!(
+            parser.parse("today + 1mo", &jan31),
+            utc(2024, 2, 29, 0, 0, 0)
+        );
+        assert!(parser.parse("+999999y", &jan31).is_err());
+    }
+
+    #[test]
+    fn test_spoken_offset() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 14, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse("in 15m"),
+            Ok("2025-10-27 14:25:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("5 minutes ago"),
+            Ok("2025-10-27 14:05:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("in 1d 2h 30m"),
+            Ok("2025-10-28 16:40:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("+1d2h30m"),
+            Ok("2025-10-28 16:40:11 +00:00".to_string())
+        );
+        // still a minute of the reference hour
+        assert_eq!(parse("30m"), Ok("2025-10-27 14:30:00 +00:00".to_string()));
+        assert!(parse("in 5 parsecs").is_err());
+        // not a duration, left to the formats
+        assert!(!matches!(
+            Parser::new().parse("in 2025", &dt),
+            Err(ParseError::InvalidDuration { .. })
+        ));
+        assert_eq!(
+            Parser::new()
+                .add_format("in %Y")
+                .parse("in 2025", &dt)
+                .map(|d| d.to_string()),
+            Ok("2025-01-01 00:00:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_anchor_with_offset() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 14, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse("tomorrow+3h"),
+            Ok("2025-10-28 03:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("tomorrow + 3h"),
+            Ok("2025-10-28 03:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("now-90m"),
+            Ok("2025-10-27 12:40:11 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("yesterday-30m"),
+            Ok("2025-10-25 23:30:00 +00:00".to_string())
+        );
+        assert_eq!(parse("Today"), Ok("2025-10-27 00:00:00 +00:00".to_string()));
+        assert!(parse("now+3x").is_err());
+    }
+
+    #[test]
+    fn test_fractional_clock() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 14, 10, 11).unwrap();
+        let fractional = Parser::new().allow_fractional_clock(true);
+        let parse = |parser: &Parser, s| parser.parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse(&fractional, "10.5"),
+            Ok("2025-10-27 10:30:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&fractional, "10.25"),
+            Ok("2025-10-27 10:15:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&fractional, "8.1"),
+            Ok("2025-10-27 08:06:00 +00:00".to_string())
+        );
+        assert!(parse(&fractional, "24.5").is_err());
+        assert!(parse(&fractional, "10.").is_err());
+        assert!(parse(&fractional, "10.5h").is_err());
+        assert!(parse(&Parser::new(), "10.5").is_err());
+        assert_eq!(
+            parse(&fractional.locale(Locale::FRENCH), "10,75"),
+            Ok("2025-10-27 10:45:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fractional_minutes() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 14, 10, 11).unwrap();
+        let fractional = Parser::new().fractional_minutes(true);
+        let parse = |parser: &Parser, s| parser.parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse(&fractional, "10:15.5"),
+            Ok("2025-10-27 10:15:30 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&fractional, "9:05.25"),
+            Ok("2025-10-27 09:05:15 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&fractional, "23:59.999"),
+            Ok("2025-10-27 23:59:59.940 +00:00".to_string())
+        );
+        assert!(parse(&fractional, "10:60.5").is_err());
+        assert!(parse(&fractional, "24:15.5").is_err());
+        assert!(parse(&fractional, "10:15.").is_err());
+        assert!(parse(&Parser::new(), "10:15.5").is_err());
+        // a fraction after the seconds is left to the formats
+        assert_eq!(
+            parse(&fractional, "10:15:30"),
+            Ok("2025-10-27 10:15:30 +00:00".to_string())
+        );
+        assert_eq!(
+            fractional.parse("10:15:30.5", &dt),
+            Parser::new().parse("10:15:30.5", &dt)
+        );
+        assert_eq!(
+            parse(&fractional.locale(Locale::FRENCH), "10:15,5"),
+            Ok("2025-10-27 10:15:30 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bare_number_as_hour() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 14, 10, 11).unwrap();
+        let parse = |parser: &Parser, s| parser.parse(s, &dt).map(|d| d.to_string());
+        let hours = Parser::new().bare_number_as_hour(true);
+
+        assert_eq!(
+            parse(&Parser::new(), "09"),
+            Ok("2025-10-27 14:09:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&hours, "09"),
+            Ok("2025-10-27 09:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&hours, "23"),
+            Ok("2025-10-27 23:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse(&hours, "7"),
+            Ok("2025-10-27 07:00:00 +00:00".to_string())
+        );
+        assert!(parse(&hours, "24").is_err());
+        assert_eq!(
+            parse(&hours, "2025"),
+            Ok("2025-01-01 00:00:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_floor_at_reference() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let floored = Parser::new().floor_at_reference(true);
+
+        assert_eq!(floored.parse("2025-10-01", &dt), Ok(dt.fixed_offset()));
+        assert_eq!(floored.parse("8h", &dt), Ok(dt.fixed_offset()));
+        assert_eq!(
+            floored.parse("10h", &dt).map(|d| d.to_string()),
+            Ok("2025-10-27 10:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            Parser::new().parse("8h", &dt).map(|d| d.to_string()),
+            Ok("2025-10-27 08:00:00 +00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_leap_seconds() {
+        let dt = Utc.with_ymd_and_hms(2016, 12, 31, 12, 0, 0).unwrap();
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_000_000_000)
+            .unwrap()
+            .and_utc()
+            .fixed_offset();
+
+        assert_eq!(
+            Parser::new().parse("2016-12-31 23:59:60 +00:00", &dt),
+            Ok(leap)
+        );
+        assert_eq!(
+            Parser::new().parse_keep_reference_zone("23:59:60", &dt),
+            Ok(leap.to_utc())
+        );
+
+        let reject = Parser::new().leap_seconds(LeapSecond::Reject);
+        let err = reject.parse("23:59:60", &dt).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::LeapSecond {
+                input: "23:59:60".to_string()
+            }
+        );
+        assert_eq!(err.to_string(), "Leap second not supported: \"23:59:60\"");
+        assert!(reject.parse("23:59:59", &dt).is_ok());
+        assert_eq!(
+            reject.parse_loose("23:59:60 rest", &dt),
+            Err(ParseError::LeapSecond {
+                input: "23:59:60 rest".to_string()
+            })
+        );
+        assert_eq!(
+            reject.parse_relative_to("23:59:60", &dt.fixed_offset()),
+            Err(ParseError::LeapSecond {
+                input: "23:59:60".to_string()
+            })
+        );
+        assert!(reject.parse_loose("23:59:59 rest", &dt).is_ok());
+        assert!(
+            reject
+                .parse_relative_to("23:59:59", &dt.fixed_offset())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_postprocess() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let to_minute =
+            |dt: DateTime<FixedOffset>| dt.with_second(0).unwrap().with_nanosecond(0).unwrap();
+        let parser = Parser::new().with_postprocess(to_minute);
+        let at = |h, m, s| {
+            Utc.with_ymd_and_hms(2025, 10, 27, h, m, s)
+                .unwrap()
+                .fixed_offset()
+        };
+
+        assert_eq!(parser.parse("now", &dt), Ok(at(9, 10, 0)));
+        assert_eq!(parser.parse("@1761556211.75", &dt), Ok(at(9, 10, 0)));
+        assert_eq!(
+            parser.parse_timespan("now..+90s", &dt),
+            Ok((at(9, 10, 0), at(9, 11, 0)))
+        );
+
+        // run in order, after the built-in options
+        let parser = parser
+            .floor_at_reference(true)
+            .with_postprocess(|dt| dt + chrono::Duration::hours(1));
+        assert_eq!(parser.parse("@0", &dt), Ok(at(10, 10, 0)));
+    }
+
+    #[test]
+    fn test_postprocess_every_entry_point() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parser = Parser::new().with_postprocess(|dt| dt + chrono::Duration::hours(100));
+        let expected = Utc
+            .with_ymd_and_hms(2025, 10, 31, 14, 0, 0)
+            .unwrap()
+            .fixed_offset();
+
+        assert_eq!(parser.parse("2025-10-27 10:00", &dt), Ok(expected));
+        assert_eq!(
+            parser.parse_loose("2025-10-27 10:00 rest", &dt),
+            Ok((expected, " rest"))
+        );
+        assert_eq!(
+            parser.parse_relative_to("10:00", &dt.fixed_offset()),
+            Ok(expected)
+        );
+
+        let details = parser.parse_detailed("2025-10-27 10:00", &dt).unwrap();
+        assert_eq!(details.datetime, expected);
+        assert_eq!(details.wall_clock, expected.naive_local());
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        // `Q<n>`: start of the nth quarter of the reference year
+        struct Quarter;
+        impl TimeToken for Quarter {
+            fn resolve(
+                &self,
+                input: &str,
+                reference: &DateTime<FixedOffset>,
+            ) -> Option<DateTime<FixedOffset>> {
+                let quarter: u32 = input.strip_prefix('Q')?.parse().ok()?;
+                let month = (1..=4).contains(&quarter).then(|| 3 * quarter - 2)?;
+                reference
+                    .timezone()
+                    .with_ymd_and_hms(reference.year(), month, 1, 0, 0, 0)
+                    .single()
+            }
+        }
+
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let day = |m, d| Utc.with_ymd_and_hms(2025, m, d, 0, 0, 0).unwrap();
+        assert!(Parser::new().parse("Q3", &dt).is_err());
+
+        let parser = Parser::new().with_matcher(Quarter);
+        assert_eq!(parser.parse("Q3", &dt), Ok(day(7, 1).into()));
+        assert_eq!(
+            parser.parse_timespan("Q2..Q3", &dt),
+            Ok((day(4, 1).into(), day(7, 1).into()))
+        );
+        // other inputs are left to the built-in grammar
+        assert!(parser.parse("Q5", &dt).is_err());
+        assert_eq!(parser.parse("2025-10-01", &dt), Ok(day(10, 1).into()));
+
+        // a parser with other options as a fallback
+        let parser = parser.with_matcher(Parser::new().locale(Locale::FRENCH));
+        assert_eq!(parser.parse("1 août", &dt), Ok(day(8, 1).into()));
+        // tried before the built-in grammar, whose dates are month first
+        assert_eq!(parser.parse("08/01", &dt), Ok(day(1, 8).into()));
+    }
+
+    #[test]
+    fn test_with_formats() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let iso = Parser::new().with_formats(&["%Y-%m-%d"]);
+        assert_eq!(
+            iso.parse("2025-01-05", &dt),
+            Ok(Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap().into())
+        );
+        assert!(iso.parse("2025-01-05 10:00:00", &dt).is_err());
+        assert!(iso.parse("Jan 5 2025", &dt).is_err());
+        assert!(iso.parse("tomorrow", &dt).is_ok());
+
+        let dotted = Parser::new().add_format("%d.%m.%Y");
+        assert!(Parser::new().parse("05.01.2025", &dt).is_err());
+        let details = dotted.parse_detailed("05.01.2025", &dt).unwrap();
+        assert_eq!(
+            details.datetime,
+            Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap()
+        );
+        assert_eq!(details.format, Some("%d.%m.%Y"));
+        assert!(dotted.parse("Jan 5 2025", &dt).is_ok());
+
+        // formats built at run time, e.g. read from a configuration file
+        let configured = vec![String::from("%d/%m/%Y")];
+        let slashed = Parser::new()
+            .with_formats(&configured)
+            .add_format(&String::from("%Y.%m.%d"));
+        drop(configured);
+        let details = slashed.parse_detailed("05/01/2025", &dt).unwrap();
+        assert_eq!(details.format, Some("%d/%m/%Y"));
+        assert_eq!(
+            slashed.parse_detailed("2025.01.05", &dt).unwrap().format,
+            Some("%Y.%m.%d")
+        );
+        // also when a copy of the parser reads the input before `UTC+1`
+        assert_eq!(
+            slashed
+                .parse_detailed("05/01/2025 UTC+1", &dt)
+                .map(|d| d.format),
+            Ok(Some("%d/%m/%Y"))
+        );
+    }
+
+    #[test]
+    fn test_prefer_utc() {
+        // already the 28th in +02:00, still the 27th in UTC
+        let dt = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 28, 0, 30, 0)
+            .unwrap();
+        let parser = Parser::new().prefer_utc(true);
+        assert_eq!(
+            parser.parse("10:00", &dt),
+            Ok(Utc.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap().into())
+        );
+        assert_eq!(
+            parser.parse("2025-07-01 10:00", &dt).map(|d| d.to_string()),
+            Ok("2025-07-01 10:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parser.parse("2025-07-01 10:00 +02:00", &dt),
+            Parser::new().parse("2025-07-01 10:00 +02:00", &dt)
+        );
+    }
+
+    #[test]
+    fn test_whole_seconds() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let whole = Parser::new().whole_seconds(true);
+        let parse = |parser: &Parser, s| parser.parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(
+            parse(&whole, "@1761556211.75"),
+            Ok("2025-10-27T09:10:11+00:00".to_string())
+        );
+        assert_eq!(
+            parse(&whole, "+1.5s"),
+            Ok("2025-10-27T09:10:12+00:00".to_string())
+        );
+        assert_eq!(
+            parse(&Parser::new(), "@1761556211.75"),
+            Ok("2025-10-27T09:10:11.750+00:00".to_string())
+        );
+        assert_eq!(
+            parse(&Parser::new(), "+1.5s"),
+            Ok("2025-10-27T09:10:12.500+00:00".to_string())
+        );
+
+        let precise = dt.with_nanosecond(123_456_789).unwrap();
+        let whole = whole.fill(Fill::Reference);
+        assert_eq!(
+            whole.parse("2025", &precise).map(|d| d.to_rfc3339()),
+            Ok("2025-10-27T09:10:11+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_utc_offset_zone() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 23, 10, 11).unwrap();
+        let parse = |s| Parser::new().parse(s, &dt).map(|d| d.to_rfc3339());
+
+        assert_eq!(
+            parse("10:15 UTC+2"),
+            Ok("2025-10-28T10:15:00+02:00".to_string())
+        );
+        assert_eq!(
+            parse("2025-10-27 10:15 GMT-5"),
+            Ok("2025-10-27T10:15:00-05:00".to_string())
+        );
+        assert_eq!(parse("GMT-5"), Ok("2025-10-27T18:10:11-05:00".to_string()));
+        assert_eq!(
+            parse("tomorrow UTC"),
+            Ok("2025-10-28T00:00:00+00:00".to_string())
+        );
+        assert!(parse("10:15 UTC+2 10:00").is_err());
+    }
+
+    #[test]
+    fn test_custom_keyword() {
+        let dt = Utc.with_ymd_and_hms(2025, 10, 27, 9, 10, 11).unwrap();
+        let parser = Parser::new()
+            .add_keyword("Shift_Start", |r| {
+                r.with_time(chrono::NaiveTime::from_hms_opt(6, 0, 0).unwrap())
+                    .unwrap()
+            })
+            .add_keyword("now", |r| *r - chrono::Duration::hours(1));
+        let parse = |s| parser.parse(s, &dt).map(|d| d.to_string());
+
+        assert_eq!(
+            parse("shift_start"),
+            Ok("2025-10-27 06:00:00 +00:00".to_string())
+        );
+        assert_eq!(
+            parse("SHIFT_START + 2h"),
+            Ok("2025-10-27 08:00:00 +00:00".to_string())
+        );
+        // custom keywords win over built-in ones
+        assert_eq!(parse("now"), Ok("2025-10-27 08:10:11 +00:00".to_string()));
+        assert_eq!(parse("today"), Ok("2025-10-27 00:00:00 +00:00".to_string()));
+        assert!(parse("shift_start 2h").is_err());
+        assert!(Parser::new().parse("shift_start", &dt).is_err());
+        assert!(
+            parser
+                .supported_syntax()
+                .keywords
+                .contains(&"shift_start".to_string())
+        );
+    }
+
+    #[test]
+    fn test_supported_syntax() {
+        let syntax = crate::supported_syntax();
+
+        assert!(syntax.formats.contains(&"%Y-%m-%d %H:%M".to_string()));
+        assert!(syntax.formats.contains(&"@%s".to_string()));
+        assert!(syntax.formats.contains(&"%m/%d".to_string()));
+        assert!(syntax.keywords.contains(&"friday".to_string()));
+        assert!(syntax.keywords.contains(&"month".to_string()));
+        assert!(
+            syntax
+                .expressions
+                .iter()
+                .any(|e| e.starts_with("start of <this|next|previous|last>"))
+        );
+        assert_eq!(syntax.timespan_separators, ["..", " to ", "->"]);
+    }
+
+    #[test]
+    fn test_default_error_is_terse() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        assert_eq!(
+            Parser::new().parse("toto", &dt),
+            Err(ParseError::NoFormatMatched {
+                input: "toto".to_string(),
+                tried: Vec::new()
+            })
+        );
+        assert_eq!(
+            Parser::new().parse("toto", &dt).map_err(|e| e.to_string()),
+            Err("Could not parse time string: \"toto\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verbose_error_lists_formats() {
+        let dt = Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap();
+        let err = Parser::new()
+            .verbose_errors(true)
+            .parse("toto", &dt)
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.starts_with("Could not parse time string: \"toto\""),
+            "{err}"
+        );
+        assert!(
+            err.contains(&format!(
+                "tried {} formats",
+                TIMEPARSER_FORMATS.len() + DateOrder::Mdy.formats().len()
+            )),
+            "{err}"
+        );
+        assert!(err.contains("\"%Y-%m-%d %H:%M\""), "{err}");
+        assert!(err.contains("\"@%s\""), "{err}");
+    }
+}
@@ -0,0 +1,531 @@
+use std::borrow::Cow;
+
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+use crate::ParseError;
+
+/// First day of the week, used by week-based expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+/// Calendar periods that relative expressions can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+pub(crate) const UNIT_NAMES: [(&str, Unit); 5] = [
+    ("hour", Unit::Hour),
+    ("day", Unit::Day),
+    ("week", Unit::Week),
+    ("month", Unit::Month),
+    ("year", Unit::Year),
+];
+
+/// Words selecting the current, following or previous period, with
+/// the number of periods they shift by.
+pub(crate) const PERIOD_QUALIFIERS: [(&str, i64); 4] =
+    [("this", 0), ("next", 1), ("previous", -1), ("last", -1)];
+
+impl Unit {
+    fn from_name(s: &str) -> Option<Self> {
+        UNIT_NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, unit)| *unit)
+    }
+}
+
+/// First instant of the `unit` period containing `naive`.
+pub(crate) fn period_start(
+    naive: NaiveDateTime,
+    unit: Unit,
+    week_start: WeekStart,
+) -> NaiveDateTime {
+    let date = naive.date();
+    match unit {
+        Unit::Hour => date.and_hms_opt(naive.hour(), 0, 0).unwrap(),
+        Unit::Day => date.and_time(NaiveTime::MIN),
+        Unit::Week => {
+            let days_in = date.weekday().days_since(week_start.weekday());
+            // going back at most 6 days from a valid date
+            (date - Days::new(days_in.into())).and_time(NaiveTime::MIN)
+        }
+        Unit::Month => date.with_day(1).unwrap().and_time(NaiveTime::MIN),
+        Unit::Year => date.with_ordinal(1).unwrap().and_time(NaiveTime::MIN),
+    }
+}
+
+/// Move `naive` by `n` periods of `unit`, `None` on overflow.
+pub(crate) fn shift_periods(naive: NaiveDateTime, unit: Unit, n: i64) -> Option<NaiveDateTime> {
+    match unit {
+        Unit::Hour => naive.checked_add_signed(chrono::Duration::try_hours(n)?),
+        Unit::Day => naive.checked_add_signed(chrono::Duration::try_days(n)?),
+        Unit::Week => naive.checked_add_signed(chrono::Duration::try_weeks(n)?),
+        Unit::Month => add_months(naive, n),
+        Unit::Year => add_months(naive, n.checked_mul(12)?),
+    }
+}
+
+// Calendar month arithmetic, clamping the day to the end of shorter
+// months (Jan 31 + 1 month is Feb 28/29).
+pub(crate) fn add_months(naive: NaiveDateTime, n: i64) -> Option<NaiveDateTime> {
+    let months = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+    if n >= 0 {
+        naive.checked_add_months(months)
+    } else {
+        naive.checked_sub_months(months)
+    }
+}
+
+/// Terse forms of `start of this <unit>` (`sod`, `sow`, `bom`, `boy`)
+/// and of the end of the current period, which is the start of the next
+/// one since periods exclude their end (`eod`, `eow`, `eom`, `eoy`).
+///
+/// They only match as the whole input, in any case.
+pub(crate) const PERIOD_ABBREVIATIONS: [(&str, Unit, i64); 8] = [
+    ("sod", Unit::Day, 0),
+    ("eod", Unit::Day, 1),
+    ("sow", Unit::Week, 0),
+    ("eow", Unit::Week, 1),
+    ("bom", Unit::Month, 0),
+    ("eom", Unit::Month, 1),
+    ("boy", Unit::Year, 0),
+    ("eoy", Unit::Year, 1),
+];
+
+/// Recognize `start of <this|next|previous|last> <unit>`, or one of
+/// the `PERIOD_ABBREVIATIONS`.
+///
+/// Returns `None` when `timestr` isn't such an expression, so the
+/// caller can try other syntaxes.
+pub(crate) fn parse_period_start(
+    timestr: &str,
+    reference: NaiveDateTime,
+    week_start: WeekStart,
+) -> Option<Result<NaiveDateTime, ParseError>> {
+    let lower = timestr.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let (unit, n) = match words[..] {
+        [abbreviation] => PERIOD_ABBREVIATIONS
+            .iter()
+            .find(|(a, _, _)| *a == abbreviation)
+            .map(|(_, unit, n)| (*unit, n))?,
+        ["start", "of", qualifier, unit] => {
+            let (_, n) = PERIOD_QUALIFIERS.iter().find(|(q, _)| *q == qualifier)?;
+            (Unit::from_name(unit)?, n)
+        }
+        _ => return None,
+    };
+
+    log::trace!("Period start: {:?} shifted by {}", unit, n);
+    Some(
+        shift_periods(period_start(reference, unit, week_start), unit, *n)
+            .ok_or_else(|| ParseError::out_of_range(timestr)),
+    )
+}
+
+/// Recognize `<next|previous|last> <unit>`, the reference moved by one
+/// period: a calendar month for `next month`, clamping the day to the
+/// end of shorter months.
+pub(crate) fn parse_period_shift(
+    timestr: &str,
+    reference: NaiveDateTime,
+) -> Option<Result<NaiveDateTime, ParseError>> {
+    let lower = timestr.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let [qualifier, unit] = words[..] else {
+        return None;
+    };
+    let (_, n) = PERIOD_QUALIFIERS
+        .iter()
+        .find(|(q, n)| *q == qualifier && *n != 0)?;
+    let unit = Unit::from_name(unit)?;
+    log::trace!("Reference shifted by {} {:?}", n, unit);
+    Some(shift_periods(reference, unit, *n).ok_or_else(|| ParseError::out_of_range(timestr)))
+}
+
+/// Words introducing the `n` periods up to the reference, as in
+/// `last 7 days`.
+pub(crate) const TRAILING_QUALIFIERS: [&str; 3] = ["last", "past", "previous"];
+
+/// Recognize `<last|past|previous> <n> <unit>[s]`, returning `n` and
+/// the unit.
+pub(crate) fn split_trailing_periods(timespan: &str) -> Option<(i64, Unit)> {
+    let lower = timespan.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let [qualifier, n, unit] = words[..] else {
+        return None;
+    };
+    if !TRAILING_QUALIFIERS.contains(&qualifier) || !n.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let unit = Unit::from_name(unit.strip_suffix('s').unwrap_or(unit))?;
+    Some((n.parse().ok()?, unit))
+}
+
+/// Keywords standing for a moment around the reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Anchor {
+    /// The reference itself.
+    Now,
+    /// Midnight, this many days from the reference day.
+    Midnight(i64),
+}
+
+pub(crate) const ANCHORS: [(&str, Anchor); 4] = [
+    ("now", Anchor::Now),
+    ("today", Anchor::Midnight(0)),
+    ("tomorrow", Anchor::Midnight(1)),
+    ("yesterday", Anchor::Midnight(-1)),
+];
+
+/// Split `<anchor>[ <+|-> <duration>]` into the anchor and the signed
+/// offset, with any whitespace removed (`tomorrow + 3h` gives `+3h`).
+///
+/// Returns `None` when `timestr` doesn't start with an anchor keyword
+/// followed by the end of input or a sign.
+pub(crate) fn split_anchor(timestr: &str) -> Option<(Anchor, String)> {
+    let (word, offset) = split_keyword(timestr)?;
+    let (_, anchor) = ANCHORS.iter().find(|(name, _)| *name == word)?;
+    Some((*anchor, offset))
+}
+
+/// Split `<today|tomorrow|yesterday> <time>` into the day, relative to
+/// the reference one, and the time.
+pub(crate) fn split_day_and_time(timestr: &str) -> Option<(i64, &str)> {
+    let (word, rest) = timestr.trim().split_once(char::is_whitespace)?;
+    let word = word.to_lowercase();
+    match ANCHORS.iter().find(|(name, _)| *name == word)? {
+        (_, Anchor::Midnight(days)) => Some((*days, rest.trim_start())),
+        (_, Anchor::Now) => None,
+    }
+}
+
+/// Drop the noise word `at` (in any case) from the start of `timestr`
+/// or from between a day and a time: `at 14:30`, `tomorrow at 15:00`.
+/// A leading `@` is read as `at` too when a clock time follows
+/// (`@14:30`); before a plain number it stays a timestamp.
+pub(crate) fn strip_at(timestr: &str) -> Cow<'_, str> {
+    if let Some(time) = timestr.strip_prefix('@')
+        && time.starts_with(|c: char| c.is_ascii_digit())
+        && time
+            .split_whitespace()
+            .next()
+            .is_some_and(|t| t.contains(':'))
+    {
+        return Cow::Borrowed(time);
+    }
+    let lower = timestr.to_ascii_lowercase();
+    if lower.starts_with("at ") {
+        return Cow::Borrowed(timestr[3..].trim_start());
+    }
+    match lower.find(" at ") {
+        Some(i) => Cow::Owned(format!(
+            "{} {}",
+            &timestr[..i],
+            timestr[i + 4..].trim_start()
+        )),
+        None => Cow::Borrowed(timestr),
+    }
+}
+
+/// Split `<word>[ <+|-> <duration>]` into the lowercased word and the
+/// signed offset, like `split_anchor` but for any word.
+pub(crate) fn split_keyword(timestr: &str) -> Option<(String, String)> {
+    let timestr = timestr.trim();
+    let end = timestr
+        .find(|c: char| c.is_whitespace() || c == '+' || c == '-')
+        .unwrap_or(timestr.len());
+    let (word, rest) = timestr.split_at(end);
+    let offset: String = rest.split_whitespace().collect();
+    if word.is_empty() || !(offset.is_empty() || offset.starts_with(['+', '-'])) {
+        return None;
+    }
+    Some((word.to_lowercase(), offset))
+}
+
+/// `next`/`last` in front of a weekday name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Qualifier {
+    Next,
+    Last,
+}
+
+pub(crate) const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// English weekday name or three-letter abbreviation, any case.
+pub(crate) fn weekday_from_name(s: &str) -> Option<Weekday> {
+    let lower = s.to_lowercase();
+    WEEKDAYS.into_iter().find(|wd| {
+        let name = long_weekday_name(*wd);
+        lower == name || lower == name[..3]
+    })
+}
+
+pub(crate) fn long_weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Split `[next|last] <weekday> <rest>` into its parts.
+pub(crate) fn split_weekday(timestr: &str) -> Option<(Option<Qualifier>, Weekday, &str)> {
+    let (first, rest) = split_word(timestr);
+    let (qualifier, (name, rest)) = match first.to_lowercase().as_str() {
+        "next" => (Some(Qualifier::Next), split_word(rest)),
+        "last" => (Some(Qualifier::Last), split_word(rest)),
+        _ => (None, (first, rest)),
+    };
+    Some((qualifier, weekday_from_name(name)?, rest))
+}
+
+fn split_word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    (&s[..end], s[end..].trim_start())
+}
+
+/// Date of `weekday` seen from `date`.
+///
+/// Without qualifier this is the nearest upcoming occurrence, `date`
+/// itself when it already is that weekday. `next` and `last` pick the
+/// occurrence in the following or previous week, as delimited by
+/// `week_start`.
+pub(crate) fn resolve_weekday(
+    date: NaiveDate,
+    weekday: Weekday,
+    qualifier: Option<Qualifier>,
+    week_start: WeekStart,
+) -> Option<NaiveDate> {
+    let weeks = match qualifier {
+        None => return date.checked_add_days(Days::new(weekday.days_since(date.weekday()).into())),
+        Some(Qualifier::Next) => 1,
+        Some(Qualifier::Last) => -1,
+    };
+    let week = period_start(date.and_time(NaiveTime::MIN), Unit::Week, week_start);
+    let target = shift_periods(week, Unit::Week, weeks)?;
+    target
+        .date()
+        .checked_add_days(Days::new(weekday.days_since(week_start.weekday()).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn naive(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, mi, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_split_weekday() {
+        assert_eq!(
+            split_weekday("Fri 17:00"),
+            Some((None, Weekday::Fri, "17:00"))
+        );
+        assert_eq!(
+            split_weekday("next monday  09:00"),
+            Some((Some(Qualifier::Next), Weekday::Mon, "09:00"))
+        );
+        assert_eq!(split_weekday("Frid 17:00"), None);
+        assert_eq!(split_weekday("17:00"), None);
+    }
+
+    #[test]
+    fn test_split_trailing_periods() {
+        assert_eq!(split_trailing_periods("last 7 days"), Some((7, Unit::Day)));
+        assert_eq!(
+            split_trailing_periods("Past 24 hours"),
+            Some((24, Unit::Hour))
+        );
+        assert_eq!(
+            split_trailing_periods("previous 1 month"),
+            Some((1, Unit::Month))
+        );
+        assert_eq!(split_trailing_periods("last week"), None);
+        assert_eq!(split_trailing_periods("last -7 days"), None);
+        assert_eq!(split_trailing_periods("next 7 days"), None);
+        assert_eq!(split_trailing_periods("last 7 fortnights"), None);
+    }
+
+    #[test]
+    fn test_split_anchor() {
+        assert_eq!(split_anchor("Now"), Some((Anchor::Now, String::new())));
+        assert_eq!(
+            split_anchor("tomorrow+3h"),
+            Some((Anchor::Midnight(1), "+3h".to_string()))
+        );
+        assert_eq!(
+            split_anchor("yesterday - 30m"),
+            Some((Anchor::Midnight(-1), "-30m".to_string()))
+        );
+        assert_eq!(split_anchor("today 10:00"), None);
+        assert_eq!(split_anchor("nowhere"), None);
+    }
+
+    #[test]
+    fn test_resolve_weekday() {
+        let wed = NaiveDate::from_ymd_opt(2025, 10, 29).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+        let resolve = |wd, q| resolve_weekday(wed, wd, q, WeekStart::Monday);
+
+        assert_eq!(resolve(Weekday::Wed, None), Some(wed));
+        assert_eq!(resolve(Weekday::Fri, None), Some(day(31)));
+        assert_eq!(
+            resolve(Weekday::Mon, None),
+            NaiveDate::from_ymd_opt(2025, 11, 3)
+        );
+        assert_eq!(
+            resolve(Weekday::Fri, Some(Qualifier::Next)),
+            NaiveDate::from_ymd_opt(2025, 11, 7)
+        );
+        assert_eq!(resolve(Weekday::Mon, Some(Qualifier::Last)), Some(day(20)));
+    }
+
+    #[test]
+    fn test_start_of_next_month_across_december() {
+        let reference = naive(2025, 12, 15, 10, 30, 0);
+        assert_eq!(
+            parse_period_start("start of next month", reference, WeekStart::Monday),
+            Some(Ok(naive(2026, 1, 1, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("Start Of Previous Month", reference, WeekStart::Monday),
+            Some(Ok(naive(2025, 11, 1, 0, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_start_of_previous_week() {
+        // Wednesday
+        let reference = naive(2025, 10, 29, 10, 30, 0);
+        assert_eq!(
+            parse_period_start("start of previous week", reference, WeekStart::Monday),
+            Some(Ok(naive(2025, 10, 20, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("start of previous week", reference, WeekStart::Sunday),
+            Some(Ok(naive(2025, 10, 19, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("start of next week", reference, WeekStart::Monday),
+            Some(Ok(naive(2025, 11, 3, 0, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_period_abbreviations() {
+        let reference = naive(2025, 10, 27, 9, 10, 11);
+        let parse = |s| parse_period_start(s, reference, WeekStart::Monday);
+
+        assert_eq!(parse("eom"), Some(Ok(naive(2025, 11, 1, 0, 0, 0))));
+        assert_eq!(parse("BOM"), Some(Ok(naive(2025, 10, 1, 0, 0, 0))));
+        assert_eq!(parse("boy"), Some(Ok(naive(2025, 1, 1, 0, 0, 0))));
+        assert_eq!(parse("eoy"), Some(Ok(naive(2026, 1, 1, 0, 0, 0))));
+        assert_eq!(parse("EOD"), Some(Ok(naive(2025, 10, 28, 0, 0, 0))));
+        assert_eq!(parse("sod"), Some(Ok(naive(2025, 10, 27, 0, 0, 0))));
+        assert_eq!(parse("eod soon"), None);
+    }
+
+    #[test]
+    fn test_period_shift() {
+        let reference = naive(2025, 3, 31, 9, 10, 11);
+        let shift = |s| parse_period_shift(s, reference);
+
+        assert_eq!(shift("next month"), Some(Ok(naive(2025, 4, 30, 9, 10, 11))));
+        assert_eq!(shift("Last Month"), Some(Ok(naive(2025, 2, 28, 9, 10, 11))));
+        assert_eq!(shift("next week"), Some(Ok(naive(2025, 4, 7, 9, 10, 11))));
+        assert_eq!(
+            shift("previous year"),
+            Some(Ok(naive(2024, 3, 31, 9, 10, 11)))
+        );
+        assert_eq!(shift("this month"), None);
+        assert_eq!(shift("next friday"), None);
+        assert_eq!(shift("last 7 days"), None);
+    }
+
+    #[test]
+    fn test_week_abbreviations() {
+        // Wednesday
+        let reference = naive(2025, 10, 29, 10, 30, 0);
+        let parse = |s, week_start| parse_period_start(s, reference, week_start);
+
+        assert_eq!(
+            parse("SOW", WeekStart::Monday),
+            Some(Ok(naive(2025, 10, 27, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse("eow", WeekStart::Monday),
+            Some(Ok(naive(2025, 11, 3, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse("sow", WeekStart::Sunday),
+            Some(Ok(naive(2025, 10, 26, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse("EOW", WeekStart::Sunday),
+            Some(Ok(naive(2025, 11, 2, 0, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_start_of_other_units() {
+        let reference = naive(2025, 10, 29, 10, 30, 0);
+        assert_eq!(
+            parse_period_start("start of this hour", reference, WeekStart::Monday),
+            Some(Ok(naive(2025, 10, 29, 10, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("start of next day", reference, WeekStart::Monday),
+            Some(Ok(naive(2025, 10, 30, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("start of last year", reference, WeekStart::Monday),
+            Some(Ok(naive(2024, 1, 1, 0, 0, 0)))
+        );
+        assert_eq!(
+            parse_period_start("start of next fortnight", reference, WeekStart::Monday),
+            None
+        );
+        assert_eq!(
+            parse_period_start("10:30", reference, WeekStart::Monday),
+            None
+        );
+    }
+}
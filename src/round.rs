@@ -0,0 +1,55 @@
+use chrono::{DateTime, Duration, DurationRound, FixedOffset};
+
+use crate::ParseError;
+
+/// Direction in which `round_to` moves a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// The closest multiple, halfway times going up.
+    #[default]
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+/// Round `dt` to a multiple of `step`, counted on its wall-clock time
+/// so that `15m` steps fall on the quarter hours in any offset.
+pub fn round_to(
+    dt: &DateTime<FixedOffset>,
+    step: Duration,
+    rounding: Rounding,
+) -> Result<DateTime<FixedOffset>, ParseError> {
+    let rounded = match rounding {
+        Rounding::Nearest => dt.duration_round(step),
+        Rounding::Floor => dt.duration_trunc(step),
+        Rounding::Ceil => dt.duration_round_up(step),
+    };
+    rounded.map_err(|source| ParseError::Rounding { step, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_round_to() {
+        let offset = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let dt = offset.with_ymd_and_hms(2025, 10, 27, 10, 7, 30).unwrap();
+        let round = |rounding| round_to(&dt, Duration::minutes(15), rounding);
+
+        assert_eq!(
+            round(Rounding::Nearest),
+            Ok(offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap())
+        );
+        assert_eq!(
+            round(Rounding::Floor),
+            Ok(offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap())
+        );
+        assert_eq!(
+            round(Rounding::Ceil),
+            Ok(offset.with_ymd_and_hms(2025, 10, 27, 10, 15, 0).unwrap())
+        );
+        assert!(round_to(&dt, Duration::zero(), Rounding::Floor).is_err());
+    }
+}
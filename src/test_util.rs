@@ -0,0 +1,55 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::parse_keep_reference_zone;
+
+/// Assert that `dt`, formatted with the strftime `fmt`, parses back to
+/// `dt` itself, to check in a downstream test suite that a format is
+/// understood by this crate.
+///
+/// The formatted string is parsed like `parse_with_reference` does,
+/// with `dt` as the reference, except that wall-clock times are placed
+/// in the offset of `dt` rather than the system timezone, so the
+/// result doesn't depend on `TZ`.
+///
+/// # Panics
+///
+/// If the string can't be parsed or gives another time or offset.
+#[track_caller]
+pub fn assert_roundtrip(dt: &DateTime<FixedOffset>, fmt: &str) {
+    let formatted = dt.format(fmt).to_string();
+    match parse_keep_reference_zone(&formatted, dt) {
+        Ok(parsed) => assert!(
+            parsed == *dt && parsed.offset() == dt.offset(),
+            "{:?} formatted with {:?} parsed back to {}, expected {}",
+            formatted,
+            fmt,
+            parsed,
+            dt
+        ),
+        Err(err) => panic!(
+            "{:?} formatted with {:?} could not be parsed back: {}",
+            formatted, fmt, err
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_roundtrip() {
+        let dt = DateTime::parse_from_rfc3339("2025-10-27T10:15:30+02:00").unwrap();
+        assert_roundtrip(&dt, "%Y-%m-%d %H:%M:%S");
+        assert_roundtrip(&dt, "%+");
+        assert_roundtrip(&dt, "%a %b %e %H:%M:%S %Y");
+        assert_roundtrip(&dt, "%H:%M:%S");
+    }
+
+    #[test]
+    #[should_panic(expected = "parsed back to 2025-10-27 10:15:00 +02:00")]
+    fn test_assert_roundtrip_lossy_format() {
+        let dt = DateTime::parse_from_rfc3339("2025-10-27T10:15:30+02:00").unwrap();
+        assert_roundtrip(&dt, "%Y-%m-%d %H:%M");
+    }
+}
@@ -0,0 +1,508 @@
+use std::ops::Deref;
+use std::str::FromStr;
+
+use chrono::{DateTime, Days, Duration, FixedOffset, Months, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::duration::describe_duration;
+use crate::{ParseError, TimespanBounds, parse_timespan};
+
+/// A timespan as a value of its own, e.g. to store it: it derefs to,
+/// and converts from and into, the `(start, stop)` tuple returned by
+/// the parsing functions.
+///
+/// With the `serde` feature, it is serialized as
+/// `{"start": "...", "stop": "..."}` with RFC 3339 bounds. `FromStr`
+/// reads the human form instead, with `parse_timespan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Bounds", into = "Bounds")
+)]
+pub struct Timespan(pub TimespanBounds);
+
+// Serialized form of `Timespan`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Bounds {
+    start: DateTime<FixedOffset>,
+    stop: DateTime<FixedOffset>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Bounds> for Timespan {
+    fn from(bounds: Bounds) -> Self {
+        Timespan((bounds.start, bounds.stop))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Timespan> for Bounds {
+    fn from(Timespan((start, stop)): Timespan) -> Self {
+        Bounds { start, stop }
+    }
+}
+
+impl Deref for Timespan {
+    type Target = TimespanBounds;
+
+    fn deref(&self) -> &TimespanBounds {
+        &self.0
+    }
+}
+
+impl From<TimespanBounds> for Timespan {
+    fn from(bounds: TimespanBounds) -> Self {
+        Timespan(bounds)
+    }
+}
+
+impl From<Timespan> for TimespanBounds {
+    fn from(span: Timespan) -> Self {
+        span.0
+    }
+}
+
+impl FromStr for Timespan {
+    type Err = ParseError;
+
+    /// Parse with `parse_timespan`, relative to the current time.
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse_timespan(s).map(Timespan)
+    }
+}
+
+/// Successive calendar days from the start of `span`, at the same
+/// wall-clock time, up to (excluding) its end.
+///
+/// Iteration stops early instead of panicking if a step would leave
+/// the range representable by `chrono`.
+pub fn iter_days(span: &TimespanBounds) -> impl Iterator<Item = DateTime<FixedOffset>> {
+    let (start, stop) = *span;
+    (0u64..)
+        .map_while(move |n| start.checked_add_days(Days::new(n)))
+        .take_while(move |dt| *dt < stop)
+}
+
+/// Successive calendar months from the start of `span`, up to
+/// (excluding) its end.
+///
+/// Each step is computed from the start, so the day of month is
+/// clamped only for the short months themselves (Jan 31, Feb 28,
+/// Mar 31, ...). Like `iter_days`, iteration stops at the last
+/// representable month.
+pub fn iter_months(span: &TimespanBounds) -> impl Iterator<Item = DateTime<FixedOffset>> {
+    let (start, stop) = *span;
+    (0u32..)
+        .map_while(move |n| start.checked_add_months(Months::new(n)))
+        .take_while(move |dt| *dt < stop)
+}
+
+/// Divide `span` into `n` contiguous sub-spans of equal length, the
+/// last one absorbing the remainder of the division.
+///
+/// Returns no bucket when `n` is 0.
+pub fn timespan_split(span: &TimespanBounds, n: usize) -> Vec<TimespanBounds> {
+    let (start, stop) = *span;
+    let Ok(count) = i32::try_from(n) else {
+        return Vec::new();
+    };
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let step = (stop - start) / count;
+    let mut buckets = Vec::with_capacity(n);
+    let mut bucket_start = start;
+    for i in 1..=count {
+        let bucket_stop = if i == count {
+            stop
+        } else {
+            bucket_start + step
+        };
+        buckets.push((bucket_start, bucket_stop));
+        bucket_start = bucket_stop;
+    }
+    buckets
+}
+
+/// Move both bounds of `span` by `by`, e.g. by minus its length to get
+/// the previous period.
+///
+/// Returns `None` if a bound would leave the representable range.
+pub fn shift_timespan(span: &TimespanBounds, by: Duration) -> Option<TimespanBounds> {
+    let (start, stop) = *span;
+    Some((start.checked_add_signed(by)?, stop.checked_add_signed(by)?))
+}
+
+/// Move the start of `span` back and its end forward by `by`, shrinking
+/// it when `by` is negative.
+///
+/// Returns `None` if a bound would leave the representable range, or if
+/// shrinking would put the end before the start.
+pub fn expand_timespan(span: &TimespanBounds, by: Duration) -> Option<TimespanBounds> {
+    let (start, stop) = *span;
+    let start = start.checked_sub_signed(by)?;
+    let stop = stop.checked_add_signed(by)?;
+    (start <= stop).then_some((start, stop))
+}
+
+/// Whether `a` and `b` share at least one instant. Spans exclude their
+/// end, so `10:00..11:00` and `11:00..12:00` don't overlap, and an
+/// empty span overlaps nothing.
+pub fn timespan_overlap(a: &TimespanBounds, b: &TimespanBounds) -> bool {
+    timespan_intersection(a, b).is_some()
+}
+
+/// The instants `a` and `b` share, `None` if they don't overlap.
+pub fn timespan_intersection(a: &TimespanBounds, b: &TimespanBounds) -> Option<TimespanBounds> {
+    let (start, stop) = (a.0.max(b.0), a.1.min(b.1));
+    (start < stop).then_some((start, stop))
+}
+
+/// `span` in words, for confirmation messages.
+///
+/// Spans running from midnight to midnight, in the offsets of their
+/// bounds, are named by their days: `the whole day 2025-10-27`,
+/// `3 days, from 2025-10-27 to 2025-10-29` (the end being excluded,
+/// the last day named is the one before it). Others give their length
+/// and bounds, the date of the end being omitted when it is the same
+/// as the start's: `2 hours, from 2025-10-27 10:00 to 12:00`.
+pub fn describe_timespan(span: &TimespanBounds) -> String {
+    let (first, last) = (span.0.naive_local(), span.1.naive_local());
+    if first.time() == NaiveTime::MIN
+        && last.time() == NaiveTime::MIN
+        && let Some(last_day) = last.date().pred_opt().filter(|day| *day >= first.date())
+    {
+        let days = (last.date() - first.date()).num_days();
+        return if days == 1 {
+            format!("the whole day {}", first.date())
+        } else {
+            format!("{days} days, from {} to {last_day}", first.date())
+        };
+    }
+
+    let clock = |dt: &NaiveDateTime| match (dt.second(), dt.nanosecond()) {
+        (0, 0) => "%H:%M",
+        (_, 0) => "%H:%M:%S",
+        _ => "%H:%M:%S%.f",
+    };
+    let end = if first.date() == last.date() {
+        last.format(clock(&last)).to_string()
+    } else {
+        format!("{} {}", last.date(), last.format(clock(&last)))
+    };
+    format!(
+        "{}, from {} {} to {end}",
+        describe_duration(span.1 - span.0),
+        first.date(),
+        first.format(clock(&first))
+    )
+}
+
+/// Instants within `span` where `zone` changes its UTC offset, in
+/// order, each given in the offset that starts there.
+///
+/// The span is sampled every hour and each change is then narrowed
+/// down to the second, so two changes less than an hour apart (which
+/// no real zone has) would be missed.
+#[cfg(feature = "chrono-tz")]
+pub fn dst_transitions_in(
+    span: &TimespanBounds,
+    zone: chrono_tz::Tz,
+) -> Vec<DateTime<FixedOffset>> {
+    use chrono::{Offset, TimeZone, Utc};
+
+    let (start, stop) = *span;
+    let offset_at = |t: DateTime<Utc>| zone.offset_from_utc_datetime(&t.naive_utc()).fix();
+
+    let mut transitions = Vec::new();
+    let mut before = start.to_utc();
+    let stop = stop.to_utc();
+    while before < stop {
+        let after = before
+            .checked_add_signed(Duration::hours(1))
+            .map_or(stop, |t| t.min(stop));
+        if offset_at(before) != offset_at(after) {
+            // the offset at `lo` is the old one, at `hi` the new one
+            let (mut lo, mut hi) = (before, after);
+            while hi - lo > Duration::seconds(1) {
+                let mid = lo + (hi - lo) / 2;
+                if offset_at(mid) == offset_at(lo) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            transitions.push(hi.with_timezone(&offset_at(hi)));
+        }
+        before = after;
+    }
+    transitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_iter_days() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 30, 0, 0, 0).unwrap(),
+        );
+        let days: Vec<_> = iter_days(&span).map(|dt| dt.to_string()).collect();
+        assert_eq!(
+            days,
+            [
+                "2025-10-27 10:00:00 +00:00",
+                "2025-10-28 10:00:00 +00:00",
+                "2025-10-29 10:00:00 +00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_months_clamps_without_drift() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 1, 31, 0, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap(),
+        );
+        let months: Vec<_> = iter_months(&span)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .collect();
+        assert_eq!(months, ["2025-01-31", "2025-02-28", "2025-03-31"]);
+    }
+
+    #[test]
+    fn test_timespan_newtype() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let bounds = (
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 27, 12, 0, 0).unwrap(),
+        );
+        let span = Timespan::from(bounds);
+        let (start, stop) = *span;
+        assert_eq!((start, stop), bounds);
+        assert_eq!(describe_timespan(&span), describe_timespan(&bounds));
+        assert_eq!(TimespanBounds::from(span), bounds);
+
+        let parsed: Timespan = "2025-10-27 10:00 +00:00..2025-10-27 12:00 +00:00"
+            .parse()
+            .unwrap();
+        assert_eq!(parsed, span);
+        assert!("nonsense".parse::<Timespan>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_timespan_serde() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let span = Timespan((
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 27, 12, 0, 0).unwrap(),
+        ));
+        let json = serde_json::to_string(&span).unwrap();
+        assert_eq!(
+            json,
+            r#"{"start":"2025-10-27T10:00:00+01:00","stop":"2025-10-27T12:00:00+01:00"}"#
+        );
+        assert_eq!(serde_json::from_str::<Timespan>(&json).unwrap(), span);
+    }
+
+    #[test]
+    fn test_describe_timespan() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let at = |d, h, m| offset.with_ymd_and_hms(2025, 10, d, h, m, 0).unwrap();
+
+        assert_eq!(
+            describe_timespan(&(at(27, 0, 0), at(28, 0, 0))),
+            "the whole day 2025-10-27"
+        );
+        assert_eq!(
+            describe_timespan(&(at(27, 0, 0), at(30, 0, 0))),
+            "3 days, from 2025-10-27 to 2025-10-29"
+        );
+        assert_eq!(
+            describe_timespan(&(at(27, 10, 0), at(27, 12, 0))),
+            "2 hours, from 2025-10-27 10:00 to 12:00"
+        );
+        assert_eq!(
+            describe_timespan(&(at(27, 22, 30), at(28, 23, 45))),
+            "1 day, 1 hour, 15 minutes, from 2025-10-27 22:30 to 2025-10-28 23:45"
+        );
+        assert_eq!(
+            describe_timespan(&(at(27, 0, 0), at(27, 0, 0))),
+            "0 seconds, from 2025-10-27 00:00 to 00:00"
+        );
+    }
+
+    #[test]
+    fn test_timespan_split_even() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 28, 0, 0, 0).unwrap(),
+        );
+        let buckets = timespan_split(&span, 4);
+
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(buckets[0].0, span.0);
+        assert_eq!(buckets[3].1, span.1);
+        for (a, b) in buckets.iter().zip(&buckets[1..]) {
+            assert_eq!(a.1, b.0);
+        }
+        assert!(buckets.iter().all(|(s, e)| *e - *s == Duration::hours(6)));
+        assert!(timespan_split(&span, 0).is_empty());
+    }
+
+    #[test]
+    fn test_timespan_split_uneven() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 10).unwrap(),
+        );
+        let buckets = timespan_split(&span, 3);
+
+        let lengths: Vec<_> = buckets.iter().map(|(s, e)| *e - *s).collect();
+        assert_eq!(
+            lengths,
+            [
+                Duration::nanoseconds(3_333_333_333),
+                Duration::nanoseconds(3_333_333_333),
+                Duration::nanoseconds(3_333_333_334),
+            ]
+        );
+        assert_eq!(buckets[0].0, span.0);
+        assert_eq!(buckets[2].1, span.1);
+        assert_eq!(buckets[0].1, buckets[1].0);
+        assert_eq!(buckets[1].1, buckets[2].0);
+    }
+
+    #[test]
+    fn test_shift_timespan_to_previous_day() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 28, 0, 0, 0).unwrap(),
+        );
+        assert_eq!(
+            shift_timespan(&span, span.0 - span.1),
+            Some((
+                offset.with_ymd_and_hms(2025, 10, 26, 0, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+            ))
+        );
+
+        let max = DateTime::<Utc>::MAX_UTC.fixed_offset();
+        assert_eq!(shift_timespan(&(max, max), Duration::seconds(1)), None);
+    }
+
+    #[test]
+    fn test_expand_timespan() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = (
+            offset.with_ymd_and_hms(2025, 10, 27, 10, 0, 0).unwrap(),
+            offset.with_ymd_and_hms(2025, 10, 27, 12, 0, 0).unwrap(),
+        );
+        assert_eq!(
+            expand_timespan(&span, Duration::hours(1)),
+            Some((
+                offset.with_ymd_and_hms(2025, 10, 27, 9, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 27, 13, 0, 0).unwrap(),
+            ))
+        );
+        assert_eq!(
+            expand_timespan(&span, Duration::hours(-1)),
+            Some((span.0 + Duration::hours(1), span.0 + Duration::hours(1)))
+        );
+        assert_eq!(expand_timespan(&span, Duration::hours(-2)), None);
+    }
+
+    #[test]
+    fn test_timespan_overlap_and_intersection() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let span = |from, to| {
+            (
+                offset.with_ymd_and_hms(2025, 10, 27, from, 0, 0).unwrap(),
+                offset.with_ymd_and_hms(2025, 10, 27, to, 0, 0).unwrap(),
+            )
+        };
+        let check = |a, b, expected: Option<TimespanBounds>| {
+            assert_eq!(timespan_overlap(&a, &b), expected.is_some());
+            assert_eq!(timespan_overlap(&b, &a), expected.is_some());
+            assert_eq!(timespan_intersection(&a, &b), expected);
+            assert_eq!(timespan_intersection(&b, &a), expected);
+        };
+
+        // disjoint
+        check(span(8, 9), span(10, 12), None);
+        // touching: the end is excluded
+        check(span(8, 10), span(10, 12), None);
+        // overlapping
+        check(span(8, 11), span(10, 12), Some(span(10, 11)));
+        // nested
+        check(span(8, 18), span(10, 12), Some(span(10, 12)));
+        check(span(10, 12), span(10, 12), Some(span(10, 12)));
+        // empty spans contain no instant
+        check(span(10, 10), span(8, 12), None);
+
+        // offsets don't matter, only instants
+        let paris = FixedOffset::east_opt(3600).unwrap();
+        let shifted = (
+            paris.with_ymd_and_hms(2025, 10, 27, 11, 0, 0).unwrap(),
+            paris.with_ymd_and_hms(2025, 10, 27, 13, 0, 0).unwrap(),
+        );
+        assert_eq!(
+            timespan_intersection(&span(8, 11), &shifted),
+            Some((shifted.0, span(8, 11).1))
+        );
+    }
+
+    #[test]
+    fn test_iter_near_representable_maximum() {
+        let max = DateTime::<Utc>::MAX_UTC.fixed_offset();
+
+        let span = (max - Duration::days(2), max);
+        assert_eq!(iter_days(&span).count(), 2);
+
+        let span = (max - Duration::days(45), max);
+        let months: Vec<_> = iter_months(&span).collect();
+        assert_eq!(months.len(), 2);
+        assert!(months.iter().all(|dt| *dt <= max));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_dst_transitions_in() {
+        let span = (
+            DateTime::parse_from_rfc3339("2025-03-01T00:00:00+01:00").unwrap(),
+            DateTime::parse_from_rfc3339("2025-04-01T00:00:00+02:00").unwrap(),
+        );
+        let transitions = dst_transitions_in(&span, chrono_tz::Europe::Paris);
+        assert_eq!(
+            transitions,
+            [DateTime::parse_from_rfc3339("2025-03-30T03:00:00+02:00").unwrap()]
+        );
+
+        let summer = shift_timespan(&span, Duration::days(60)).unwrap();
+        assert!(dst_transitions_in(&summer, chrono_tz::Europe::Paris).is_empty());
+
+        let year = (
+            DateTime::parse_from_rfc3339("2025-01-01T00:00:00-05:00").unwrap(),
+            DateTime::parse_from_rfc3339("2026-01-01T00:00:00-05:00").unwrap(),
+        );
+        let transitions: Vec<String> = dst_transitions_in(&year, chrono_tz::America::New_York)
+            .iter()
+            .map(|t| t.to_rfc3339())
+            .collect();
+        assert_eq!(
+            transitions,
+            ["2025-03-09T03:00:00-04:00", "2025-11-02T01:00:00-05:00"]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::process::Command;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use kal_time::parse_timespan_with_reference;
+
+fn kt_parse(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_kt-parse"))
+        .args(args)
+        .output()
+        .expect("run kt-parse");
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).expect("utf-8 stdout"),
+        String::from_utf8(output.stderr).expect("utf-8 stderr"),
+    )
+}
+
+#[test]
+fn test_default_precision_is_seconds() {
+    let (ok, stdout, _) = kt_parse(&["time", "30m", "2025-10-22T09:10:11+00:00"]);
+    assert!(ok);
+    assert_eq!(stdout, "1761125400 2025-10-22 09:30:00 +00:00\n");
+}
+
+#[test]
+fn test_precision_ms() {
+    let (ok, stdout, _) = kt_parse(&["--precision", "ms", "time", "@1704150000"]);
+    assert!(ok);
+    assert_eq!(stdout, "1704150000 2024-01-01 23:00:00.000 +00:00\n");
+}
+
+#[test]
+fn test_precision_invalid() {
+    let (ok, _, stderr) = kt_parse(&["--precision=cs", "time", "@1704150000"]);
+    assert!(!ok);
+    assert!(stderr.contains("Invalid precision"), "{stderr}");
+}
+
+#[test]
+fn test_reference_flag_chains_timespan_bounds() {
+    let output = Command::new(env!("CARGO_BIN_EXE_kt-parse"))
+        .env("TZ", "UTC")
+        .args([
+            "--reference",
+            "2025-10-27 12:00",
+            "timespan",
+            "2025-10-20 10:00..14:00",
+        ])
+        .output()
+        .expect("run kt-parse");
+    assert!(output.status.success());
+
+    let reference = Utc.with_ymd_and_hms(2025, 10, 27, 12, 0, 0).unwrap();
+    let (start, stop) =
+        parse_timespan_with_reference("2025-10-20 10:00..14:00", &reference).unwrap();
+    let format = |dt: DateTime<FixedOffset>| {
+        format!("{} {}", dt.timestamp(), dt.format("%Y-%m-%d %H:%M:%S %:z"))
+    };
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n{}\n", format(start), format(stop))
+    );
+}
+
+#[test]
+fn test_reference_flag_and_argument_conflict() {
+    let (ok, _, stderr) = kt_parse(&[
+        "--reference=2025-10-27T12:00:00+00:00",
+        "time",
+        "10h",
+        "2025-10-27T12:00:00+00:00",
+    ]);
+    assert!(!ok);
+    assert!(stderr.contains("--reference"), "{stderr}");
+}
+
+#[test]
+fn test_round() {
+    let reference = "--reference=2025-10-27T09:00:00+00:00";
+    let (ok, stdout, _) = kt_parse(&[reference, "round", "10:07", "15m"]);
+    assert!(ok);
+    assert_eq!(stdout, "1761559200 2025-10-27 10:00:00 +00:00\n");
+
+    let (ok, stdout, _) = kt_parse(&[reference, "--ceil", "round", "10:07", "15m"]);
+    assert!(ok);
+    assert_eq!(stdout, "1761560100 2025-10-27 10:15:00 +00:00\n");
+
+    let (ok, stdout, _) = kt_parse(&[
+        "--floor",
+        "round",
+        "10:14",
+        "15m",
+        "2025-10-27T09:00:00+00:00",
+    ]);
+    assert!(ok);
+    assert_eq!(stdout, "1761559200 2025-10-27 10:00:00 +00:00\n");
+
+    let (ok, _, stderr) = kt_parse(&[reference, "round", "10:07", "15x"]);
+    assert!(!ok);
+    assert!(stderr.contains("Invalid step"), "{stderr}");
+}
+
+#[test]
+fn test_json_output() {
+    let (ok, stdout, _) = kt_parse(&["--json", "time", "30m", "2025-10-22T09:10:11+00:00"]);
+    assert!(ok);
+    assert_eq!(
+        stdout,
+        "{\"timestamp\": 1761125400, \"time\": \"2025-10-22 09:30:00 +00:00\"}\n"
+    );
+
+    let (ok, stdout, _) = kt_parse(&[
+        "--json",
+        "timespan",
+        "10:00..11:00",
+        "2025-10-22T09:10:11+00:00",
+    ]);
+    assert!(ok);
+    assert_eq!(
+        stdout,
+        "{\"start\": {\"timestamp\": 1761127200, \"time\": \"2025-10-22 10:00:00 +00:00\"}, \
+         \"stop\": {\"timestamp\": 1761130800, \"time\": \"2025-10-22 11:00:00 +00:00\"}}\n"
+    );
+}
+
+#[test]
+fn test_json_error() {
+    let (ok, stdout, stderr) =
+        kt_parse(&["--json", "time", "someday", "2025-10-22T09:10:11+00:00"]);
+    assert!(!ok);
+    assert_eq!(
+        stdout,
+        "{\"error\": \"Failed to parse time: Could not parse time string: \\\"someday\\\"\"}\n"
+    );
+    assert!(stderr.is_empty(), "{stderr}");
+
+    let (ok, stdout, _) = kt_parse(&["time", "--json"]);
+    assert!(!ok);
+    assert!(
+        stdout.starts_with("{\"error\": \"Usage: kt-parse"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,45 @@
+// Tests needing the system timezone to be Europe/London. They live in
+// their own binary because `TZ` is process-wide, and the unit tests set
+// it to Europe/Paris.
+
+use chrono::{Local, TimeZone};
+use kal_time::parse_with_reference;
+
+fn set_london() {
+    unsafe {
+        std::env::set_var("TZ", "Europe/London");
+    }
+}
+
+#[test]
+fn test_resolution_ignores_reference_season() {
+    set_london();
+    let winter = Local.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+    let summer = Local.with_ymd_and_hms(2025, 7, 15, 12, 0, 0).unwrap();
+    // London is at UTC+0 in winter, which used to select UTC
+    assert_eq!(winter.offset().local_minus_utc(), 0);
+
+    for reference in [winter, summer] {
+        let parse = |s| parse_with_reference(s, &reference).map(|dt| dt.to_string());
+        assert_eq!(
+            parse("2025-07-22 03:17"),
+            Ok("2025-07-22 03:17:00 +01:00".to_string()),
+            "reference {reference}"
+        );
+        assert_eq!(
+            parse("2025-01-22 03:17"),
+            Ok("2025-01-22 03:17:00 +00:00".to_string()),
+            "reference {reference}"
+        );
+    }
+}
+
+#[test]
+fn test_calendar_offset_keeps_london_wall_clock() {
+    set_london();
+    let winter = Local.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+    assert_eq!(
+        parse_with_reference("+6mo", &winter).map(|dt| dt.to_string()),
+        Ok("2025-07-15 12:00:00 +01:00".to_string())
+    );
+}